@@ -33,6 +33,12 @@ pub const REPROCESS_SLOT_BUFFER: u64 = 6;
 pub const REPROCESS_MAX_MULTIPLIER: u64 = 100;
 pub const REPROCESS_FEE: u64 = LAMPORTS_PER_SOL / 200;
 
+/// The weight of the CHROMIUM reward for reprocessing a COAL proof, in basis points.
+pub const COAL_REPROCESS_WEIGHT: u64 = 10_000;
+
+/// The weight of the CHROMIUM reward for reprocessing a WOOD proof, in basis points.
+pub const WOOD_REPROCESS_WEIGHT: u64 = 5_000;
+
 /// The liveness tolerance for WOOD in seconds.
 pub const WOOD_LIVENESS_TOLERANCE: i64 = 65;
 
@@ -49,6 +55,9 @@ pub const ONE_COAL: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
 /// One WOOD token, denominated in indivisible units.
 pub const ONE_WOOD: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
 
+/// One CHROMIUM token, denominated in indivisible units.
+pub const ONE_CHROMIUM: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
+
 /// The duration of one minute, in seconds.
 pub const ONE_MINUTE: i64 = 60;
 
@@ -61,6 +70,18 @@ pub const WOOD_EPOCH_DURATION: i64 = ONE_MINUTE * EPOCH_MINUTES;
 /// The maximum token supply (21 million).
 pub const MAX_COAL_SUPPLY: u64 = ONE_COAL * 21_000_000;
 
+/// The number of COAL emission halvings.
+pub const COAL_HALVING_COUNT: usize = 8;
+
+/// The COAL supply thresholds at which the per-epoch emission target halves. Like Bitcoin, every era
+/// mines half of the supply which remains below the max supply.
+pub const COAL_HALVING_THRESHOLDS: [u64; COAL_HALVING_COUNT] = array_const_fn_init![const_coal_halving_threshold; 8];
+
+/// Function to derive const halving thresholds.
+const fn const_coal_halving_threshold(i: usize) -> u64 {
+    MAX_COAL_SUPPLY - (MAX_COAL_SUPPLY >> (i + 1))
+}
+
 /// The target quantity of COAL to be mined per minute.
 pub const TARGET_COAL_REWARDS: u64 = ONE_COAL.saturating_mul(1000).saturating_div(256);
 
@@ -85,9 +106,25 @@ pub const MAX_COAL_EPOCH_REWARDS: u64 = TARGET_COAL_EPOCH_REWARDS * BUS_COUNT as
 /// The quantity of COAL each bus is allowed to issue per epoch.
 pub const BUS_COAL_EPOCH_REWARDS: u64 = MAX_COAL_EPOCH_REWARDS / BUS_COUNT as u64;
 
-/// The number of bus accounts, for parallelizing mine operations.
+/// The default number of bus accounts, for parallelizing mine operations.
 pub const BUS_COUNT: usize = 8;
 
+/// The maximum number of bus accounts a resource can be configured with.
+///
+/// Reset locks every bus of a resource, and reset all locks the buses of both resources. With 16 buses each,
+/// reset all references 48 accounts, which leaves room below the limit of 64 accounts per transaction.
+pub const MAX_BUS_COUNT: usize = 16;
+
+/// The share of the epoch rewards, in basis points, allocated to buses in proportion to their theoretical
+/// rewards of the previous epoch. The rest is spread evenly so idle buses are never left empty.
+pub const BUS_REBALANCE_SHARE: u64 = 5_000;
+
+/// The number of epochs kept in an epoch history account.
+pub const EPOCH_HISTORY_LENGTH: usize = 64;
+
+/// The number of entries in a leaderboard account.
+pub const LEADERBOARD_LENGTH: usize = 32;
+
 /// The smoothing factor for reward rate changes. The reward rate cannot change by mCOAL or less
 /// than a factor of this constant from one epoch to the next.
 pub const SMOOTHING_FACTOR: u64 = 2;
@@ -123,6 +160,30 @@ pub const WOOD_PROOF: &[u8] = b"wood_proof";
 pub const COAL_MAIN_HAND_TOOL: &[u8] = b"coal_main_hand_tool";
 pub const WOOD_MAIN_HAND_TOOL: &[u8] = b"wood_main_hand_tool";
 
+/// The seed of the epoch history account PDAs.
+pub const EPOCH_HISTORY: &[u8] = b"epoch_history";
+
+/// The seed of the leaderboard account PDAs.
+pub const LEADERBOARD: &[u8] = b"leaderboard";
+
+/// The seed of the resource config account PDAs.
+pub const RESOURCE_CONFIG: &[u8] = b"resource_config";
+
+/// The seed of the CHROMIUM config account PDA.
+pub const CHROMIUM_CONFIG: &[u8] = b"chromium_config";
+
+/// The seed of the CHROMIUM stake account PDAs.
+pub const CHROMIUM_STAKE: &[u8] = b"chromium_stake";
+
+/// The seed of the delegation account PDAs.
+pub const DELEGATION: &[u8] = b"delegation";
+
+/// The seed of the proof transfer account PDAs.
+pub const PROOF_TRANSFER: &[u8] = b"proof_transfer";
+
+/// The seed of the miner stats account PDAs.
+pub const MINER_STATS: &[u8] = b"miner_stats";
+
 /// The seed of the treasury account PDA.
 pub const TREASURY: &[u8] = b"treasury";
 
@@ -164,9 +225,18 @@ pub const FORGE_PICKAXE_COLLECTION: Pubkey = pubkey!("CuaLHUJA1dyQ6AYcTcMZrCoBqs
 pub const BASE_TOOL_MULTIPLIER: u64 = 300;
 pub const MAX_TOOL_MULTIPLIER: u64 = 600;
 
+/// The max bonus earned by staking CHROMIUM, in basis points of the mining reward.
+pub const MAX_CHROMIUM_STAKE_MULTIPLIER: u64 = 2_500;
+
+/// The quantity of staked CHROMIUM which earns the max bonus.
+pub const CHROMIUM_STAKE_TARGET: u64 = ONE_CHROMIUM * 1_000;
+
+/// The time unstaked CHROMIUM waits before it can be withdrawn, in seconds.
+pub const CHROMIUM_UNSTAKE_COOLDOWN: i64 = ONE_MINUTE * 60 * 24 * 3;
+
 /// The addresses of the bus accounts.
-pub const COAL_BUS_ADDRESSES: [Pubkey; BUS_COUNT] = array_const_fn_init![const_coal_bus_address; 8];
-pub const WOOD_BUS_ADDRESSES: [Pubkey; BUS_COUNT] = array_const_fn_init![const_wood_bus_address; 8];
+pub const COAL_BUS_ADDRESSES: [Pubkey; MAX_BUS_COUNT] = array_const_fn_init![const_coal_bus_address; 16];
+pub const WOOD_BUS_ADDRESSES: [Pubkey; MAX_BUS_COUNT] = array_const_fn_init![const_wood_bus_address; 16];
 
 /// Function to derive const bus addresses.
 const fn const_coal_bus_address(i: usize) -> Pubkey {
@@ -183,6 +253,10 @@ pub const COAL_CONFIG_ADDRESS: Pubkey =
 pub const WOOD_CONFIG_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[WOOD_CONFIG], &PROGRAM_ID).0);
 
+/// The address of the CHROMIUM config account.
+pub const CHROMIUM_CONFIG_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[CHROMIUM_CONFIG], &PROGRAM_ID).0);
+
 /// The address of the mint metadata account.
 pub const COAL_METADATA_ADDRESS: Pubkey = Pubkey::new_from_array(
     ed25519::derive_program_address(
@@ -219,6 +293,54 @@ pub const WOOD_MINT_ADDRESS: Pubkey =
 pub const CHROMIUM_MINT_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[CHROMIUM_MINT, &MINT_NOISE], &PROGRAM_ID).0);
 
+/// The address of the COAL epoch history account.
+pub const COAL_EPOCH_HISTORY_ADDRESS: Pubkey = Pubkey::new_from_array(
+    ed25519::derive_program_address(
+        &[
+            EPOCH_HISTORY,
+            unsafe { &*(&COAL_MINT_ADDRESS as *const Pubkey as *const [u8; 32]) },
+        ],
+        &PROGRAM_ID,
+    )
+    .0,
+);
+
+/// The address of the WOOD epoch history account.
+pub const WOOD_EPOCH_HISTORY_ADDRESS: Pubkey = Pubkey::new_from_array(
+    ed25519::derive_program_address(
+        &[
+            EPOCH_HISTORY,
+            unsafe { &*(&WOOD_MINT_ADDRESS as *const Pubkey as *const [u8; 32]) },
+        ],
+        &PROGRAM_ID,
+    )
+    .0,
+);
+
+/// The address of the COAL resource config account.
+pub const COAL_RESOURCE_CONFIG_ADDRESS: Pubkey = Pubkey::new_from_array(
+    ed25519::derive_program_address(
+        &[
+            RESOURCE_CONFIG,
+            unsafe { &*(&COAL_MINT_ADDRESS as *const Pubkey as *const [u8; 32]) },
+        ],
+        &PROGRAM_ID,
+    )
+    .0,
+);
+
+/// The address of the WOOD resource config account.
+pub const WOOD_RESOURCE_CONFIG_ADDRESS: Pubkey = Pubkey::new_from_array(
+    ed25519::derive_program_address(
+        &[
+            RESOURCE_CONFIG,
+            unsafe { &*(&WOOD_MINT_ADDRESS as *const Pubkey as *const [u8; 32]) },
+        ],
+        &PROGRAM_ID,
+    )
+    .0,
+);
+
 /// The address of the treasury account.
 pub const TREASURY_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[TREASURY], &PROGRAM_ID).0);
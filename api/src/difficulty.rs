@@ -0,0 +1,198 @@
+/// The number of fractional bits of a difficulty multiplier.
+pub const DIFFICULTY_MULTIPLIER_PRECISION: u32 = 16;
+
+/// The easiest difficulty target, which accepts every hash with a leading zero bit.
+pub const MAX_DIFFICULTY_TARGET: u128 = 1 << 127;
+
+/// The factor applied to the base reward rate when the difficulty target is first set, in basis points.
+///
+/// Hashes meeting the whole-bit min difficulty used to pay the base reward rate, but pay between 1x and 2x
+/// against the equivalent target, or `2 ln 2` on average. The base reward rate is scaled by `1 / (2 ln 2)` so
+/// the emission is unchanged.
+pub const DIFFICULTY_TARGET_CALIBRATION: u64 = 7_213;
+
+/// Returns the leading 128 bits of a hash, read as a big-endian integer. Only these bits are significant at
+/// any practical difficulty.
+pub fn hash_prefix(hash: &[u8; 32]) -> u128 {
+    u128::from_be_bytes(hash[..16].try_into().unwrap())
+}
+
+/// Returns the difficulty target accepting exactly the hashes with at least `min_difficulty` leading zero bits.
+pub fn difficulty_target(min_difficulty: u64) -> u128 {
+    let min_difficulty = min_difficulty.clamp(1, 128) as u32;
+    1u128 << (128 - min_difficulty)
+}
+
+/// Returns the whole-bit difficulty every hash meeting the target has, as reported in events and history.
+pub fn min_difficulty(target: u128) -> u64 {
+    target.saturating_sub(1).leading_zeros() as u64
+}
+
+/// Returns whether a hash meets the difficulty target.
+pub fn meets_difficulty_target(hash: &[u8; 32], target: u128) -> bool {
+    hash_prefix(hash).lt(&target)
+}
+
+/// Returns the reward multiplier of a hash as a fixed-point number with `DIFFICULTY_MULTIPLIER_PRECISION`
+/// fractional bits.
+///
+/// The multiplier is `target / hash`, where the hash is read as a big-endian integer. Rewards grow smoothly with
+/// the hash value instead of doubling for every leading zero bit, and a hash exactly at the target pays the base
+/// reward rate.
+pub fn difficulty_multiplier(hash: &[u8; 32], target: u128) -> u128 {
+    let hash_prefix = hash_prefix(hash) >> DIFFICULTY_MULTIPLIER_PRECISION;
+    target.saturating_div(hash_prefix.max(1))
+}
+
+/// Returns the reward of a hash for the given base reward rate and difficulty target.
+pub fn calculate_hash_reward(base_reward_rate: u64, hash: &[u8; 32], target: u128) -> u64 {
+    (base_reward_rate as u128)
+        .saturating_mul(difficulty_multiplier(hash, target))
+        .checked_shr(DIFFICULTY_MULTIPLIER_PRECISION)
+        .unwrap_or(0)
+        .min(u64::MAX as u128) as u64
+}
+
+/// Rescales the difficulty target and the base reward rate when the rate leaves the given bounds.
+///
+/// The rate is pinned to the bound it crossed and the target is scaled by the same ratio, so the difficulty
+/// moves by a fraction of a bit and the reward of a hash is unchanged. If the target hits its own bounds, the
+/// rate absorbs the rest of the adjustment.
+pub fn adjust_difficulty_target(
+    target: u128,
+    base_reward_rate: u64,
+    min_reward_rate: u64,
+    max_reward_rate: u64,
+) -> (u128, u64) {
+    let bound = if base_reward_rate.lt(&min_reward_rate) {
+        min_reward_rate
+    } else if base_reward_rate.gt(&max_reward_rate) {
+        max_reward_rate
+    } else {
+        return (target, base_reward_rate);
+    };
+    let scaled_target = scale_target(target, base_reward_rate, bound);
+    let new_target = scaled_target.clamp(1, MAX_DIFFICULTY_TARGET);
+    if new_target.eq(&scaled_target) {
+        return (new_target, bound);
+    }
+    (new_target, scale_reward_rate(base_reward_rate, target, new_target))
+}
+
+/// Returns `target * numerator / denominator`, saturating on overflow.
+fn scale_target(target: u128, numerator: u64, denominator: u64) -> u128 {
+    let denominator = denominator.max(1) as u128;
+    let numerator = numerator as u128;
+    (target / denominator)
+        .checked_mul(numerator)
+        .and_then(|quotient| quotient.checked_add((target % denominator) * numerator / denominator))
+        .unwrap_or(u128::MAX)
+}
+
+/// Returns `base_reward_rate * target / new_target`, keeping the 64 significant bits of the targets.
+fn scale_reward_rate(base_reward_rate: u64, target: u128, new_target: u128) -> u64 {
+    let shift = (128 - target.max(new_target).leading_zeros()).saturating_sub(64);
+    let target = target >> shift;
+    let new_target = (new_target >> shift).max(1);
+    ((base_reward_rate as u128) * target / new_target).clamp(1, u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a hash with `zeros` leading zero bits followed by the given byte pattern.
+    fn hash_with(zeros: usize, fill: u8) -> [u8; 32] {
+        let mut hash = [fill; 32];
+        for bit in 0..zeros {
+            hash[bit / 8] &= !(0x80 >> (bit % 8));
+        }
+        hash[zeros / 8] |= 0x80 >> (zeros % 8);
+        hash
+    }
+
+    #[test]
+    fn test_difficulty_target() {
+        let target = difficulty_target(10);
+        assert_eq!(min_difficulty(target), 10);
+        assert!(meets_difficulty_target(&hash_with(10, 0xff), target));
+        assert!(!meets_difficulty_target(&hash_with(9, 0x00), target));
+
+        // A fractional target accepts a fraction of the hashes with its whole-bit difficulty.
+        let target = target / 3 * 2;
+        assert_eq!(min_difficulty(target), 10);
+        assert!(meets_difficulty_target(&hash_with(10, 0x00), target));
+        assert!(!meets_difficulty_target(&hash_with(10, 0xff), target));
+    }
+
+    #[test]
+    fn test_difficulty_multiplier_at_target() {
+        // A hash exactly at the target pays the base reward rate.
+        let target = difficulty_target(10) / 3 * 2;
+        let mut hash = [0xff; 32];
+        hash[..16].copy_from_slice(&(target - 1).to_be_bytes());
+        assert_eq!(difficulty_multiplier(&hash, target) >> DIFFICULTY_MULTIPLIER_PRECISION, 1);
+    }
+
+    #[test]
+    fn test_difficulty_multiplier_exact_bits() {
+        // A hash equal to 2^(255 - d) has an exact difficulty of d + 1.
+        let multiplier = difficulty_multiplier(&hash_with(10, 0x00), difficulty_target(10));
+        assert_eq!(multiplier, 2 << DIFFICULTY_MULTIPLIER_PRECISION);
+        let multiplier = difficulty_multiplier(&hash_with(13, 0x00), difficulty_target(10));
+        assert_eq!(multiplier, 16 << DIFFICULTY_MULTIPLIER_PRECISION);
+    }
+
+    #[test]
+    fn test_difficulty_multiplier_is_smooth() {
+        // Rewards grow with every bit of the hash, not only with its leading zeros.
+        let low = difficulty_multiplier(&hash_with(10, 0xc0), difficulty_target(10));
+        let high = difficulty_multiplier(&hash_with(10, 0x40), difficulty_target(10));
+        assert!(low.gt(&(1 << DIFFICULTY_MULTIPLIER_PRECISION)));
+        assert!(low.lt(&high));
+        assert!(high.lt(&(2 << DIFFICULTY_MULTIPLIER_PRECISION)));
+    }
+
+    #[test]
+    fn test_calculate_hash_reward() {
+        assert_eq!(calculate_hash_reward(100, &hash_with(12, 0x00), difficulty_target(10)), 800);
+        assert_eq!(calculate_hash_reward(u64::MAX, &[0; 32], MAX_DIFFICULTY_TARGET), u64::MAX);
+    }
+
+    #[test]
+    fn test_adjust_difficulty_target() {
+        let target = difficulty_target(10);
+
+        // Within bounds, nothing changes.
+        assert_eq!(adjust_difficulty_target(target, 150, 100, 200), (target, 150));
+
+        // A low rate makes the target harder by the same ratio, instead of a whole bit.
+        let (new_target, rate) = adjust_difficulty_target(target, 75, 100, 200);
+        assert_eq!(rate, 100);
+        assert_eq!(new_target, target / 4 * 3);
+        assert_eq!(min_difficulty(new_target), 10);
+
+        // A high rate makes the target easier.
+        let (new_target, rate) = adjust_difficulty_target(target, 300, 100, 200);
+        assert_eq!(rate, 200);
+        assert_eq!(new_target, target / 2 * 3);
+        assert_eq!(min_difficulty(new_target), 9);
+
+        // The reward of a hash is unchanged by the adjustment, up to rounding.
+        let hash = hash_with(12, 0x55);
+        let reward = calculate_hash_reward(300 << 20, &hash, target);
+        let new_reward = calculate_hash_reward(200 << 20, &hash, target / 2 * 3);
+        assert!(reward.abs_diff(new_reward).lt(&(reward / 10_000)));
+    }
+
+    #[test]
+    fn test_adjust_difficulty_target_clamped() {
+        // The target cannot get easier than the max, so the rate absorbs the rest.
+        let (new_target, rate) = adjust_difficulty_target(MAX_DIFFICULTY_TARGET / 2, 400, 100, 200);
+        assert_eq!(new_target, MAX_DIFFICULTY_TARGET);
+        assert_eq!(rate, 200);
+        let (new_target, rate) = adjust_difficulty_target(MAX_DIFFICULTY_TARGET, 400, 100, 200);
+        assert_eq!(new_target, MAX_DIFFICULTY_TARGET);
+        assert_eq!(rate, 400);
+    }
+}
@@ -0,0 +1,440 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::{consts::*, state::BPS_DENOMINATOR};
+
+/// An era of a halving emission schedule.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EmissionEra {
+    /// The number of halvings which have occurred.
+    pub index: u64,
+
+    /// The target quantity to be mined per epoch, used to update the base reward rate.
+    pub target_epoch_rewards: u64,
+
+    /// The quantity each bus is allowed to issue per epoch.
+    pub bus_epoch_rewards: u64,
+
+    /// The maximum quantity that can be mined per epoch across all buses.
+    pub max_epoch_rewards: u64,
+}
+
+/// The state of the buses at the end of an epoch, passed to an emission model at reset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EmissionEpoch {
+    /// The token supply after the rewards of the epoch have been minted.
+    pub supply: u64,
+
+    /// The rewards mined in the epoch.
+    pub epoch_rewards: u64,
+
+    /// The rewards left in all buses at the end of the epoch.
+    pub remaining_rewards: u64,
+
+    /// The number of buses.
+    pub bus_count: u64,
+}
+
+/// An emission model decides how many rewards each bus holds for the next epoch.
+pub trait EmissionModel {
+    /// Returns the rewards a bus holds for the next epoch, given the rewards it had left.
+    fn bus_rewards(&self, epoch: &EmissionEpoch, bus_remaining_rewards: u64) -> u64;
+
+    /// Returns the most rewards a bus can hold for the next epoch, once rebalanced.
+    fn max_bus_rewards(&self, epoch: &EmissionEpoch) -> u64;
+
+    /// Returns the target quantity to be mined per epoch, used to update the base reward rate.
+    fn target_rewards(&self, next_epoch_rewards: u64, bus_count: u64) -> u64 {
+        next_epoch_rewards.saturating_div(bus_count.max(1))
+    }
+
+    /// Returns the max bus rewards to be stored in the resource config for the following epochs, if the model
+    /// updates them.
+    fn next_max_bus_epoch_rewards(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The emission models a resource can select.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum EmissionModelKind {
+    Fixed = 0,
+    Propagation = 1,
+    Halving = 2,
+    Decaying = 3,
+}
+
+/// Tops up every bus with the same quantity each epoch.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedEmission {
+    pub bus_epoch_rewards: u64,
+}
+
+impl EmissionModel for FixedEmission {
+    fn bus_rewards(&self, _epoch: &EmissionEpoch, _bus_remaining_rewards: u64) -> u64 {
+        self.bus_epoch_rewards
+    }
+
+    fn max_bus_rewards(&self, _epoch: &EmissionEpoch) -> u64 {
+        self.bus_epoch_rewards
+    }
+}
+
+/// Carries the remaining rewards over to the next epoch, spread evenly across buses, and grows each bus by
+/// a fraction of its own remaining rewards. The growth doubles if nothing was mined in the epoch.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PropagationEmission {
+    /// The divisor of a bus's remaining rewards added as growth.
+    pub propagation_rate: u64,
+    pub min_bus_epoch_rewards: u64,
+    pub max_bus_epoch_rewards: u64,
+}
+
+impl EmissionModel for PropagationEmission {
+    fn bus_rewards(&self, epoch: &EmissionEpoch, bus_remaining_rewards: u64) -> u64 {
+        let propagation_rate = if epoch.epoch_rewards.eq(&0) {
+            self.propagation_rate / 2
+        } else {
+            self.propagation_rate
+        };
+        epoch
+            .remaining_rewards
+            .saturating_div(epoch.bus_count.max(1))
+            .saturating_add(bus_remaining_rewards.saturating_div(propagation_rate.max(1)))
+            .max(self.min_bus_epoch_rewards)
+            .min(self.max_bus_epoch_rewards)
+    }
+
+    fn max_bus_rewards(&self, _epoch: &EmissionEpoch) -> u64 {
+        self.max_bus_epoch_rewards
+    }
+}
+
+/// Halves the bus rewards every time the supply crosses half of the supply remaining below the max supply,
+/// and stops emission once the max supply is reached.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HalvingEmission {
+    pub initial_bus_epoch_rewards: u64,
+    pub max_supply: u64,
+
+    /// The number of halvings after which the bus rewards stay constant until the max supply is reached.
+    pub halving_count: u64,
+}
+
+impl HalvingEmission {
+    /// Returns the number of halvings which have occurred at the given supply.
+    pub fn halvings(&self, supply: u64) -> u64 {
+        (0..self.halving_count.min(u64::BITS as u64))
+            .take_while(|i| {
+                let remaining_supply = self.max_supply.checked_shr(*i as u32 + 1).unwrap_or(0);
+                supply.ge(&(self.max_supply - remaining_supply))
+            })
+            .count() as u64
+    }
+}
+
+impl HalvingEmission {
+    /// Returns the emission era for the given supply and bus count. The per-epoch targets halve every time
+    /// the supply crosses a halving threshold, and drop to zero once the max supply is reached.
+    pub fn era(&self, supply: u64, bus_count: u64) -> EmissionEra {
+        let epoch = EmissionEpoch {
+            supply,
+            epoch_rewards: 0,
+            remaining_rewards: 0,
+            bus_count,
+        };
+        let bus_epoch_rewards = self.max_bus_rewards(&epoch);
+        let max_epoch_rewards = bus_epoch_rewards.saturating_mul(bus_count);
+        EmissionEra {
+            index: self.halvings(supply),
+            target_epoch_rewards: self.target_rewards(max_epoch_rewards, bus_count),
+            bus_epoch_rewards,
+            max_epoch_rewards,
+        }
+    }
+}
+
+impl EmissionModel for HalvingEmission {
+    fn bus_rewards(&self, epoch: &EmissionEpoch, _bus_remaining_rewards: u64) -> u64 {
+        if epoch.supply.ge(&self.max_supply) {
+            return 0;
+        }
+        self.initial_bus_epoch_rewards >> self.halvings(epoch.supply)
+    }
+
+    fn max_bus_rewards(&self, epoch: &EmissionEpoch) -> u64 {
+        self.bus_rewards(epoch, 0)
+    }
+}
+
+/// Tops up every bus with the max bus rewards, which decay every epoch without dropping below the min bus
+/// rewards.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DecayingEmission {
+    pub max_bus_epoch_rewards: u64,
+    pub min_bus_epoch_rewards: u64,
+
+    /// The share of the max bus rewards which decays every epoch, in basis points.
+    pub epoch_rewards_decay: u64,
+}
+
+impl DecayingEmission {
+    /// Returns the max bus rewards after one epoch of decay.
+    pub fn decayed_max_bus_epoch_rewards(&self) -> u64 {
+        let decay = (self.max_bus_epoch_rewards as u128)
+            .saturating_mul(self.epoch_rewards_decay as u128)
+            .saturating_div(BPS_DENOMINATOR as u128) as u64;
+        self.max_bus_epoch_rewards
+            .saturating_sub(decay)
+            .max(self.min_bus_epoch_rewards)
+    }
+}
+
+impl EmissionModel for DecayingEmission {
+    fn bus_rewards(&self, _epoch: &EmissionEpoch, _bus_remaining_rewards: u64) -> u64 {
+        self.decayed_max_bus_epoch_rewards()
+    }
+
+    fn max_bus_rewards(&self, _epoch: &EmissionEpoch) -> u64 {
+        self.decayed_max_bus_epoch_rewards()
+    }
+
+    fn next_max_bus_epoch_rewards(&self) -> Option<u64> {
+        Some(self.decayed_max_bus_epoch_rewards())
+    }
+}
+
+/// Reallocates the rewards the emission model gave each bus for the next epoch by the buses' share of the
+/// theoretical rewards of the previous epoch, so busy buses are topped up more than idle ones. Each bus keeps
+/// all but `BUS_REBALANCE_SHARE` of its rewards, and the pooled rest is allocated by share. If nothing was
+/// mined, every bus keeps its rewards.
+///
+/// No bus is allocated more than `max_bus_rewards`. The excess of a capped bus is spread over the buses
+/// below the cap, and dropped once every bus is capped. The allocations are rounded down, so their sum may
+/// fall short of the total rewards by a few units.
+pub fn rebalance_bus_rewards(bus_rewards: &[u64], bus_theoretical_rewards: &[u64], max_bus_rewards: u64) -> Vec<u64> {
+    let total_theoretical_rewards = bus_theoretical_rewards
+        .iter()
+        .map(|theoretical_rewards| *theoretical_rewards as u128)
+        .sum::<u128>();
+    let share_of = |rewards: u128| {
+        if total_theoretical_rewards.eq(&0) {
+            0
+        } else {
+            rewards * BUS_REBALANCE_SHARE as u128 / BPS_DENOMINATOR as u128
+        }
+    };
+    let share_rewards = bus_rewards
+        .iter()
+        .map(|rewards| share_of(*rewards as u128))
+        .sum::<u128>();
+    let mut allocations: Vec<u128> = bus_rewards
+        .iter()
+        .zip(bus_theoretical_rewards)
+        .map(|(rewards, theoretical_rewards)| {
+            let share = share_rewards
+                .checked_mul(*theoretical_rewards as u128)
+                .and_then(|n| n.checked_div(total_theoretical_rewards))
+                .unwrap_or(0);
+            (*rewards as u128 - share_of(*rewards as u128)).saturating_add(share)
+        })
+        .collect();
+
+    // Spread the excess of capped buses over the buses below the cap.
+    let max_bus_rewards = max_bus_rewards as u128;
+    let mut excess = 0u128;
+    for allocation in allocations.iter_mut() {
+        excess = excess.saturating_add(allocation.saturating_sub(max_bus_rewards));
+        *allocation = (*allocation).min(max_bus_rewards);
+    }
+    loop {
+        let open_count = allocations.iter().filter(|a| a.lt(&&max_bus_rewards)).count() as u128;
+        let spread = excess.checked_div(open_count).unwrap_or(0);
+        if spread.eq(&0) {
+            break;
+        }
+        excess -= spread * open_count;
+        for allocation in allocations.iter_mut().filter(|a| (**a).lt(&max_bus_rewards)) {
+            let added = spread.min(max_bus_rewards - *allocation);
+            *allocation += added;
+            excess += spread - added;
+        }
+    }
+    allocations.into_iter().map(|allocation| allocation as u64).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch(supply: u64, epoch_rewards: u64, remaining_rewards: u64) -> EmissionEpoch {
+        EmissionEpoch {
+            supply,
+            epoch_rewards,
+            remaining_rewards,
+            bus_count: BUS_COUNT as u64,
+        }
+    }
+
+    fn coal_emission() -> HalvingEmission {
+        HalvingEmission {
+            initial_bus_epoch_rewards: BUS_COAL_EPOCH_REWARDS,
+            max_supply: MAX_COAL_SUPPLY,
+            halving_count: COAL_HALVING_COUNT as u64,
+        }
+    }
+
+    #[test]
+    fn test_halving_era_initial() {
+        let era = coal_emission().era(0, BUS_COUNT as u64);
+        assert_eq!(era.index, 0);
+        assert_eq!(era.target_epoch_rewards, TARGET_COAL_EPOCH_REWARDS);
+        assert_eq!(era.bus_epoch_rewards, BUS_COAL_EPOCH_REWARDS);
+        assert_eq!(era.max_epoch_rewards, MAX_COAL_EPOCH_REWARDS);
+    }
+
+    #[test]
+    fn test_halving_era_halvings() {
+        let model = coal_emission();
+        let bus_count = BUS_COUNT as u64;
+        assert_eq!(model.era(MAX_COAL_SUPPLY / 2 - 1, bus_count).index, 0);
+        assert_eq!(model.era(MAX_COAL_SUPPLY / 2, bus_count).index, 1);
+        assert_eq!(model.era(MAX_COAL_SUPPLY / 2, bus_count).target_epoch_rewards, TARGET_COAL_EPOCH_REWARDS / 2);
+        assert_eq!(model.era(COAL_HALVING_THRESHOLDS[2], bus_count).bus_epoch_rewards, BUS_COAL_EPOCH_REWARDS / 8);
+        assert_eq!(model.era(MAX_COAL_SUPPLY, bus_count).index, COAL_HALVING_COUNT as u64);
+    }
+
+    #[test]
+    fn test_halving_era_config() {
+        // The era follows the configured bus count and halving count.
+        let model = HalvingEmission { halving_count: 1, ..coal_emission() };
+        let era = model.era(COAL_HALVING_THRESHOLDS[2], 16);
+        assert_eq!(era.index, 1);
+        assert_eq!(era.bus_epoch_rewards, BUS_COAL_EPOCH_REWARDS / 2);
+        assert_eq!(era.max_epoch_rewards, BUS_COAL_EPOCH_REWARDS / 2 * 16);
+        assert_eq!(era.target_epoch_rewards, BUS_COAL_EPOCH_REWARDS / 2);
+    }
+
+    #[test]
+    fn test_halving_era_max_supply() {
+        let model = coal_emission();
+        assert!(model.era(MAX_COAL_SUPPLY - 1, BUS_COUNT as u64).max_epoch_rewards.gt(&0));
+        let era = model.era(MAX_COAL_SUPPLY, BUS_COUNT as u64);
+        assert_eq!(era.target_epoch_rewards, 0);
+        assert_eq!(era.bus_epoch_rewards, 0);
+        assert_eq!(era.max_epoch_rewards, 0);
+    }
+
+    #[test]
+    fn test_fixed_emission() {
+        let model = FixedEmission { bus_epoch_rewards: 100 };
+        assert_eq!(model.bus_rewards(&epoch(0, 0, 0), 0), 100);
+        assert_eq!(model.bus_rewards(&epoch(1000, 800, 0), 50), 100);
+        assert_eq!(model.target_rewards(800, 8), 100);
+    }
+
+    #[test]
+    fn test_propagation_emission() {
+        let model = PropagationEmission {
+            propagation_rate: WOOD_PROPOGATION_RATE,
+            min_bus_epoch_rewards: 10,
+            max_bus_epoch_rewards: 10_000,
+        };
+        // Remaining rewards are spread evenly and each bus grows by 5% of its remaining rewards.
+        assert_eq!(model.bus_rewards(&epoch(0, 100, 8000), 2000), 1100);
+        // Growth doubles if nothing was mined.
+        assert_eq!(model.bus_rewards(&epoch(0, 0, 8000), 2000), 1200);
+        // Bus rewards are clamped.
+        assert_eq!(model.bus_rewards(&epoch(0, 100, 0), 0), 10);
+        assert_eq!(model.bus_rewards(&epoch(0, 100, 800_000), 0), 10_000);
+    }
+
+    #[test]
+    fn test_halving_emission_thresholds() {
+        let model = coal_emission();
+        for (i, threshold) in COAL_HALVING_THRESHOLDS.iter().enumerate() {
+            assert_eq!(model.bus_rewards(&epoch(threshold - 1, 0, 0), 0), BUS_COAL_EPOCH_REWARDS >> i);
+            assert_eq!(model.bus_rewards(&epoch(*threshold, 0, 0), 0), BUS_COAL_EPOCH_REWARDS >> (i + 1));
+        }
+        assert_eq!(model.bus_rewards(&epoch(MAX_COAL_SUPPLY, 0, 0), 0), 0);
+    }
+
+    #[test]
+    fn test_halving_emission_count() {
+        let model = HalvingEmission {
+            initial_bus_epoch_rewards: 1 << 20,
+            max_supply: 1000,
+            halving_count: 2,
+        };
+        assert_eq!(model.halvings(500), 1);
+        assert_eq!(model.halvings(750), 2);
+        assert_eq!(model.halvings(999), 2);
+
+        // The halvings are bounded by the bits of the max supply.
+        let model = HalvingEmission { halving_count: u64::MAX, ..model };
+        assert_eq!(model.halvings(999), 9);
+        assert_eq!(model.halvings(u64::MAX), u64::BITS as u64);
+    }
+
+    #[test]
+    fn test_decaying_emission() {
+        let model = DecayingEmission {
+            max_bus_epoch_rewards: 1000,
+            min_bus_epoch_rewards: 10,
+            epoch_rewards_decay: 1000,
+        };
+        assert_eq!(model.bus_rewards(&epoch(0, 0, 4000), 500), 900);
+        assert_eq!(model.next_max_bus_epoch_rewards(), Some(900));
+
+        // The max bus rewards never decay below the min bus rewards.
+        let model = DecayingEmission { epoch_rewards_decay: BPS_DENOMINATOR, ..model };
+        assert_eq!(model.next_max_bus_epoch_rewards(), Some(10));
+        let model = DecayingEmission { epoch_rewards_decay: 0, ..model };
+        assert_eq!(model.next_max_bus_epoch_rewards(), Some(1000));
+
+        // Other models leave the max bus rewards unchanged.
+        assert_eq!(FixedEmission { bus_epoch_rewards: 100 }.next_max_bus_epoch_rewards(), None);
+    }
+
+    #[test]
+    fn test_rebalance_bus_rewards() {
+        // Half is kept by each bus and half is allocated by share of theoretical rewards.
+        assert_eq!(rebalance_bus_rewards(&[250; 4], &[300, 100, 0, 0], u64::MAX), vec![500, 250, 125, 125]);
+        // Every bus keeps its rewards if nothing was mined.
+        assert_eq!(rebalance_bus_rewards(&[250; 4], &[0, 0, 0, 0], u64::MAX), vec![250, 250, 250, 250]);
+        // Allocations never exceed the rewards.
+        let allocations = rebalance_bus_rewards(&[333, 334, 334], &[7, 3, 1], u64::MAX);
+        assert!(allocations.iter().sum::<u64>().le(&1001));
+        assert!(rebalance_bus_rewards(&[], &[], u64::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_rebalance_bus_rewards_per_bus() {
+        // The rewards the model gave each bus are kept in proportion, rather than spread evenly.
+        assert_eq!(rebalance_bus_rewards(&[400, 200], &[0, 0], u64::MAX), vec![400, 200]);
+        assert_eq!(rebalance_bus_rewards(&[400, 200], &[1, 1], u64::MAX), vec![350, 250]);
+    }
+
+    #[test]
+    fn test_rebalance_bus_rewards_cap() {
+        // The excess of a capped bus is spread over the other buses.
+        assert_eq!(rebalance_bus_rewards(&[250; 4], &[300, 100, 0, 0], 400), vec![400, 283, 158, 158]);
+
+        // No bus exceeds the max of the model, even if every bus is capped.
+        let model = PropagationEmission {
+            propagation_rate: WOOD_PROPOGATION_RATE,
+            min_bus_epoch_rewards: 10,
+            max_bus_epoch_rewards: 1_000,
+        };
+        let epoch = epoch(0, 100, 6_000);
+        let bus_rewards: Vec<u64> = [2_000, 1_000, 0, 0, 0, 0, 0, 0]
+            .iter()
+            .map(|remaining| model.bus_rewards(&epoch, *remaining))
+            .collect();
+        for theoretical_rewards in [[1, 0, 0, 0, 0, 0, 0, 0], [5, 5, 5, 5, 5, 5, 5, 5], [0; 8]] {
+            let allocations = rebalance_bus_rewards(&bus_rewards, &theoretical_rewards, model.max_bus_rewards(&epoch));
+            assert!(allocations.iter().all(|a| a.le(&1_000)));
+            assert!(allocations.iter().sum::<u64>().le(&bus_rewards.iter().sum::<u64>()));
+        }
+    }
+}
@@ -25,6 +25,24 @@ pub enum CoalError {
     SlotTooEarly = 8,
     #[error("The resource is invalid")]
     InvalidResource = 9,
+    #[error("The revealed secret does not match the commitment")]
+    InvalidReveal = 10,
+    #[error("Slot too late")]
+    SlotTooLate = 11,
+    #[error("The unstake cooldown has not ended")]
+    CooldownActive = 12,
+    #[error("The reprocess can still be finalized")]
+    ReprocessActive = 13,
+    #[error("The delegation has no free slot")]
+    TooManyDelegates = 14,
+    #[error("The delegate is not allowed to use this account")]
+    DelegateRestricted = 15,
+    #[error("The CHROMIUM stake of the proof must be withdrawn first")]
+    StakeNotWithdrawn = 16,
+    #[error("The CHROMIUM mint cap of the epoch or the max supply has been reached")]
+    MintCapReached = 17,
+    #[error("The proof has a pending reprocess, which must be finalized or cancelled first")]
+    ReprocessPending = 18,
 }
 
 impl From<CoalError> for ProgramError {
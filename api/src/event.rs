@@ -1,6 +1,38 @@
 use bytemuck::{Pod, Zeroable};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
-use crate::utils::impl_to_bytes;
+use crate::utils::{impl_event_log, impl_to_bytes, Discriminator};
+
+/// Events are logged with `sol_log_data` as a single buffer. The first byte is the event
+/// discriminator and the remaining bytes are the event data.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum EventDiscriminator {
+    Mine = 0,
+    Claim = 1,
+    Close = 2,
+    Open = 3,
+    Reset = 4,
+    Stake = 5,
+    Update = 6,
+    Equip = 7,
+    Unequip = 8,
+    InitReprocess = 9,
+    Reprocess = 10,
+    Unstake = 11,
+    Withdraw = 12,
+    WithdrawFees = 13,
+    CancelReprocess = 14,
+    SetDelegate = 15,
+    TransferProof = 16,
+    ProposeProofTransfer = 17,
+    AcceptProofTransfer = 18,
+    CancelProofTransfer = 19,
+    OpenMinerStats = 20,
+    InitLeaderboard = 21,
+    MergeLeaderboards = 22,
+}
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
@@ -12,4 +44,517 @@ pub struct MineEvent {
     pub stake_reward: u64,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ClaimEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The mint of the claimed resource.
+    pub resource: Pubkey,
+
+    /// The token account receiving the claim.
+    pub beneficiary: Pubkey,
+
+    /// The quantity of tokens claimed.
+    pub amount: u64,
+
+    /// The proof balance after the claim.
+    pub balance: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CloseEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The mint of the proof resource.
+    pub resource: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct OpenEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The mint of the proof resource.
+    pub resource: Pubkey,
+
+    /// The keypair which has permission to submit hashes for mining.
+    pub miner: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ResetEvent {
+    /// The mint of the reset resource.
+    pub resource: Pubkey,
+
+    /// The timestamp of the reset.
+    pub reset_at: i64,
+
+    /// The rewards the busses would have paid out in the last epoch if there were no limit.
+    pub theoretical_rewards: u64,
+
+    /// The rewards actually paid out in the last epoch.
+    pub rewards: u64,
+
+    /// The rewards left in the busses at the end of the last epoch.
+    pub remaining_rewards: u64,
+
+    /// The base reward rate for the next epoch.
+    pub base_reward_rate: u64,
+
+    /// The minimum accepted difficulty for the next epoch.
+    pub min_difficulty: u64,
+
+    /// The largest known stake balance from the last epoch.
+    pub top_balance: u64,
+
+    /// The quantity of tokens minted to the treasury.
+    pub minted: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct StakeEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The mint of the staked resource.
+    pub resource: Pubkey,
+
+    /// The quantity of tokens staked.
+    pub amount: u64,
+
+    /// The proof balance after the stake.
+    pub balance: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UpdateEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The mint of the proof resource.
+    pub resource: Pubkey,
+
+    /// The new keypair which has permission to submit hashes for mining.
+    pub miner: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct EquipEvent {
+    /// The tool authority.
+    pub authority: Pubkey,
+
+    /// The mint of the resource the tool is used for.
+    pub resource: Pubkey,
+
+    /// The equipped asset.
+    pub asset: Pubkey,
+
+    /// The durability of the tool.
+    pub durability: u64,
+
+    /// The multiplier of the tool.
+    pub multiplier: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UnequipEvent {
+    /// The tool authority.
+    pub authority: Pubkey,
+
+    /// The unequipped asset.
+    pub asset: Pubkey,
+
+    /// The remaining durability written back to the asset.
+    pub durability: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct InitReprocessEvent {
+    /// The reprocess authority.
+    pub authority: Pubkey,
+
+    /// The slot after which the reprocess can be finalized.
+    pub target_slot: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ReprocessEvent {
+    /// The reprocess authority.
+    pub authority: Pubkey,
+
+    /// The hashes reprocessed from the proof.
+    pub hashes: u64,
+
+    /// The rewards reprocessed from the proof.
+    pub rewards: u64,
+
+    /// The random multiplier applied to the reward.
+    pub multiplier: u64,
+
+    /// The quantity of CHROMIUM minted.
+    pub reward: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UnstakeEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The mint of the unstaked resource.
+    pub resource: Pubkey,
+
+    /// The quantity of tokens unstaked.
+    pub amount: u64,
+
+    /// The stake balance after the unstake.
+    pub balance: u64,
+
+    /// The quantity of tokens waiting for the cooldown to end.
+    pub unstaking_balance: u64,
+
+    /// The time the unstaking balance can be withdrawn at.
+    pub withdrawable_at: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct WithdrawEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The mint of the withdrawn resource.
+    pub resource: Pubkey,
+
+    /// The token account receiving the withdrawal.
+    pub beneficiary: Pubkey,
+
+    /// The quantity of tokens withdrawn.
+    pub amount: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct WithdrawFeesEvent {
+    /// The account receiving the fees.
+    pub beneficiary: Pubkey,
+
+    /// The quantity of lamports withdrawn.
+    pub amount: u64,
+
+    /// The lifetime quantity of lamports withdrawn after the withdrawal.
+    pub fees_withdrawn: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CancelReprocessEvent {
+    /// The reprocess authority.
+    pub authority: Pubkey,
+
+    /// The proof of the reprocess.
+    pub proof: Pubkey,
+
+    /// The hashes consumed from the proof without a reward.
+    pub hashes: u64,
+
+    /// The rewards consumed from the proof without a reward.
+    pub rewards: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SetDelegateEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The proof the delegate can mine with.
+    pub proof: Pubkey,
+
+    /// The delegate key.
+    pub delegate: Pubkey,
+
+    /// The time the delegate expires at. An expiry in the past revokes the delegate.
+    pub expires_at: i64,
+
+    /// The restrictions of the delegate, as a bitmask of `DelegateRestriction`.
+    pub restrictions: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct TransferProofEvent {
+    /// The old proof authority.
+    pub authority: Pubkey,
+
+    /// The new proof authority.
+    pub new_authority: Pubkey,
+
+    /// The closed proof of the old authority.
+    pub proof: Pubkey,
+
+    /// The proof of the new authority.
+    pub new_proof: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ProposeProofTransferEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The proposed new authority.
+    pub new_authority: Pubkey,
+
+    /// The proof to transfer.
+    pub proof: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct AcceptProofTransferEvent {
+    /// The old proof authority.
+    pub authority: Pubkey,
+
+    /// The new proof authority which accepted the transfer.
+    pub new_authority: Pubkey,
+
+    /// The closed proof of the old authority.
+    pub proof: Pubkey,
+
+    /// The proof of the new authority.
+    pub new_proof: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CancelProofTransferEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The proof of the cancelled transfer.
+    pub proof: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct OpenMinerStatsEvent {
+    /// The proof authority.
+    pub authority: Pubkey,
+
+    /// The proof the miner stats track.
+    pub proof: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct InitLeaderboardEvent {
+    /// The mint of the leaderboard resource.
+    pub resource: Pubkey,
+
+    /// The bus of the leaderboard, or the default address for the leaderboard of the resource.
+    pub bus: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MergeLeaderboardsEvent {
+    /// The mint of the leaderboard resource.
+    pub resource: Pubkey,
+
+    /// The start of the epoch the leaderboard of the resource holds after the merge.
+    pub epoch_start_at: i64,
+
+    /// The number of bus leaderboards merged.
+    pub merged: u64,
+}
+
+/// A decoded program event.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoalEvent {
+    Mine(MineEvent),
+    Claim(ClaimEvent),
+    Close(CloseEvent),
+    Open(OpenEvent),
+    Reset(ResetEvent),
+    Stake(StakeEvent),
+    Update(UpdateEvent),
+    Equip(EquipEvent),
+    Unequip(UnequipEvent),
+    InitReprocess(InitReprocessEvent),
+    Reprocess(ReprocessEvent),
+    Unstake(UnstakeEvent),
+    Withdraw(WithdrawEvent),
+    WithdrawFees(WithdrawFeesEvent),
+    CancelReprocess(CancelReprocessEvent),
+    SetDelegate(SetDelegateEvent),
+    TransferProof(TransferProofEvent),
+    ProposeProofTransfer(ProposeProofTransferEvent),
+    AcceptProofTransfer(AcceptProofTransferEvent),
+    CancelProofTransfer(CancelProofTransferEvent),
+    OpenMinerStats(OpenMinerStatsEvent),
+    InitLeaderboard(InitLeaderboardEvent),
+    MergeLeaderboards(MergeLeaderboardsEvent),
+}
+
+impl CoalEvent {
+    /// Decodes an event from the data of a `Program data:` log.
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let (discriminator, data) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let event = match EventDiscriminator::try_from(*discriminator)
+            .or(Err(ProgramError::InvalidAccountData))?
+        {
+            EventDiscriminator::Mine => CoalEvent::Mine(read_event(data)?),
+            EventDiscriminator::Claim => CoalEvent::Claim(read_event(data)?),
+            EventDiscriminator::Close => CoalEvent::Close(read_event(data)?),
+            EventDiscriminator::Open => CoalEvent::Open(read_event(data)?),
+            EventDiscriminator::Reset => CoalEvent::Reset(read_event(data)?),
+            EventDiscriminator::Stake => CoalEvent::Stake(read_event(data)?),
+            EventDiscriminator::Update => CoalEvent::Update(read_event(data)?),
+            EventDiscriminator::Equip => CoalEvent::Equip(read_event(data)?),
+            EventDiscriminator::Unequip => CoalEvent::Unequip(read_event(data)?),
+            EventDiscriminator::InitReprocess => CoalEvent::InitReprocess(read_event(data)?),
+            EventDiscriminator::Reprocess => CoalEvent::Reprocess(read_event(data)?),
+            EventDiscriminator::Unstake => CoalEvent::Unstake(read_event(data)?),
+            EventDiscriminator::Withdraw => CoalEvent::Withdraw(read_event(data)?),
+            EventDiscriminator::WithdrawFees => CoalEvent::WithdrawFees(read_event(data)?),
+            EventDiscriminator::CancelReprocess => CoalEvent::CancelReprocess(read_event(data)?),
+            EventDiscriminator::SetDelegate => CoalEvent::SetDelegate(read_event(data)?),
+            EventDiscriminator::TransferProof => CoalEvent::TransferProof(read_event(data)?),
+            EventDiscriminator::ProposeProofTransfer => CoalEvent::ProposeProofTransfer(read_event(data)?),
+            EventDiscriminator::AcceptProofTransfer => CoalEvent::AcceptProofTransfer(read_event(data)?),
+            EventDiscriminator::CancelProofTransfer => CoalEvent::CancelProofTransfer(read_event(data)?),
+            EventDiscriminator::OpenMinerStats => CoalEvent::OpenMinerStats(read_event(data)?),
+            EventDiscriminator::InitLeaderboard => CoalEvent::InitLeaderboard(read_event(data)?),
+            EventDiscriminator::MergeLeaderboards => CoalEvent::MergeLeaderboards(read_event(data)?),
+        };
+        Ok(event)
+    }
+}
+
+fn read_event<T: Pod>(data: &[u8]) -> Result<T, ProgramError> {
+    bytemuck::try_pod_read_unaligned::<T>(data).or(Err(ProgramError::InvalidAccountData))
+}
+
+macro_rules! impl_event_discriminator {
+    ($struct_name:ident, $discriminator:expr) => {
+        impl Discriminator for $struct_name {
+            fn discriminator() -> u8 {
+                $discriminator.into()
+            }
+        }
+    };
+}
+
+impl_event_discriminator!(MineEvent, EventDiscriminator::Mine);
+impl_event_discriminator!(ClaimEvent, EventDiscriminator::Claim);
+impl_event_discriminator!(CloseEvent, EventDiscriminator::Close);
+impl_event_discriminator!(OpenEvent, EventDiscriminator::Open);
+impl_event_discriminator!(ResetEvent, EventDiscriminator::Reset);
+impl_event_discriminator!(StakeEvent, EventDiscriminator::Stake);
+impl_event_discriminator!(UpdateEvent, EventDiscriminator::Update);
+impl_event_discriminator!(EquipEvent, EventDiscriminator::Equip);
+impl_event_discriminator!(UnequipEvent, EventDiscriminator::Unequip);
+impl_event_discriminator!(InitReprocessEvent, EventDiscriminator::InitReprocess);
+impl_event_discriminator!(ReprocessEvent, EventDiscriminator::Reprocess);
+impl_event_discriminator!(UnstakeEvent, EventDiscriminator::Unstake);
+impl_event_discriminator!(WithdrawEvent, EventDiscriminator::Withdraw);
+impl_event_discriminator!(WithdrawFeesEvent, EventDiscriminator::WithdrawFees);
+impl_event_discriminator!(CancelReprocessEvent, EventDiscriminator::CancelReprocess);
+impl_event_discriminator!(SetDelegateEvent, EventDiscriminator::SetDelegate);
+impl_event_discriminator!(TransferProofEvent, EventDiscriminator::TransferProof);
+impl_event_discriminator!(ProposeProofTransferEvent, EventDiscriminator::ProposeProofTransfer);
+impl_event_discriminator!(AcceptProofTransferEvent, EventDiscriminator::AcceptProofTransfer);
+impl_event_discriminator!(CancelProofTransferEvent, EventDiscriminator::CancelProofTransfer);
+impl_event_discriminator!(OpenMinerStatsEvent, EventDiscriminator::OpenMinerStats);
+impl_event_discriminator!(InitLeaderboardEvent, EventDiscriminator::InitLeaderboard);
+impl_event_discriminator!(MergeLeaderboardsEvent, EventDiscriminator::MergeLeaderboards);
+
 impl_to_bytes!(MineEvent);
+impl_to_bytes!(ClaimEvent);
+impl_to_bytes!(CloseEvent);
+impl_to_bytes!(OpenEvent);
+impl_to_bytes!(ResetEvent);
+impl_to_bytes!(StakeEvent);
+impl_to_bytes!(UpdateEvent);
+impl_to_bytes!(EquipEvent);
+impl_to_bytes!(UnequipEvent);
+impl_to_bytes!(InitReprocessEvent);
+impl_to_bytes!(ReprocessEvent);
+impl_to_bytes!(UnstakeEvent);
+impl_to_bytes!(WithdrawEvent);
+impl_to_bytes!(WithdrawFeesEvent);
+impl_to_bytes!(CancelReprocessEvent);
+impl_to_bytes!(SetDelegateEvent);
+impl_to_bytes!(TransferProofEvent);
+impl_to_bytes!(ProposeProofTransferEvent);
+impl_to_bytes!(AcceptProofTransferEvent);
+impl_to_bytes!(CancelProofTransferEvent);
+impl_to_bytes!(OpenMinerStatsEvent);
+impl_to_bytes!(InitLeaderboardEvent);
+impl_to_bytes!(MergeLeaderboardsEvent);
+
+impl_event_log!(MineEvent);
+impl_event_log!(ClaimEvent);
+impl_event_log!(CloseEvent);
+impl_event_log!(OpenEvent);
+impl_event_log!(ResetEvent);
+impl_event_log!(StakeEvent);
+impl_event_log!(UpdateEvent);
+impl_event_log!(EquipEvent);
+impl_event_log!(UnequipEvent);
+impl_event_log!(InitReprocessEvent);
+impl_event_log!(ReprocessEvent);
+impl_event_log!(UnstakeEvent);
+impl_event_log!(WithdrawEvent);
+impl_event_log!(WithdrawFeesEvent);
+impl_event_log!(CancelReprocessEvent);
+impl_event_log!(SetDelegateEvent);
+impl_event_log!(TransferProofEvent);
+impl_event_log!(ProposeProofTransferEvent);
+impl_event_log!(AcceptProofTransferEvent);
+impl_event_log!(CancelProofTransferEvent);
+impl_event_log!(OpenMinerStatsEvent);
+impl_event_log!(InitLeaderboardEvent);
+impl_event_log!(MergeLeaderboardsEvent);
+
+#[cfg(test)]
+mod tests {
+    use solana_program::pubkey::Pubkey;
+
+    use super::{ClaimEvent, CoalEvent, EventDiscriminator};
+
+    #[test]
+    fn test_decode_event() {
+        let event = ClaimEvent {
+            authority: Pubkey::new_unique(),
+            resource: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            amount: 100,
+            balance: 200,
+        };
+        let data = [&[EventDiscriminator::Claim as u8], event.to_bytes()].concat();
+        assert_eq!(CoalEvent::try_from_bytes(&data), Ok(CoalEvent::Claim(event)));
+    }
+
+    #[test]
+    fn test_decode_event_invalid() {
+        assert!(CoalEvent::try_from_bytes(&[]).is_err());
+        assert!(CoalEvent::try_from_bytes(&[u8::MAX]).is_err());
+        assert!(CoalEvent::try_from_bytes(&[EventDiscriminator::Claim as u8, 0, 0]).is_err());
+    }
+}
@@ -3,12 +3,15 @@ use drillx::Solution;
 use num_enum::TryFromPrimitive;
 use solana_program::{
     instruction::{AccountMeta, Instruction},
+    keccak,
     pubkey::Pubkey,
     system_program, sysvar,
 };
 
 use crate::{
     consts::*,
+    emission::EmissionModelKind,
+    state::leaderboard_pda,
     utils::{impl_instruction_from_bytes, impl_to_bytes},
 };
 
@@ -29,10 +32,28 @@ pub enum CoalInstruction {
     Unequip = 9,
     InitReprocess = 10,
     FinalizeReprocess = 11,
+    OpenMinerStats = 12,
+    ResetAll = 13,
+    StakeChromium = 14,
+    UnstakeChromium = 15,
+    WithdrawChromium = 16,
+    CancelReprocess = 17,
+    SetDelegate = 18,
+    TransferProof = 19,
+    ProposeProofTransfer = 20,
+    AcceptProofTransfer = 21,
+    MergeLeaderboards = 22,
+    CancelProofTransfer = 23,
     // Admin
     // InitCoal = 100,
     // InitWood = 101,
     InitChromium = 102,
+    InitEpochHistory = 103,
+    InitLeaderboard = 104,
+    SetResourceConfig = 105,
+    InitBus = 106,
+    SetChromiumConfig = 107,
+    WithdrawFees = 108,
 }
 
 impl CoalInstruction {
@@ -66,6 +87,49 @@ pub struct InitChromiumArgs {
     pub treasury_bump: u8,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitEpochHistoryArgs {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitLeaderboardArgs {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetResourceConfigArgs {
+    pub bump: u8,
+    pub emission_model: u8,
+    pub max_supply: [u8; 8],
+    pub min_bus_epoch_rewards: [u8; 8],
+    pub max_bus_epoch_rewards: [u8; 8],
+    pub epoch_rewards_decay: [u8; 8],
+    pub epoch_duration: [u8; 8],
+    pub bus_count: [u8; 8],
+    pub reset_bounty: [u8; 8],
+    pub halving_count: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetChromiumConfigArgs {
+    pub bump: u8,
+    pub max_supply: [u8; 8],
+    pub epoch_mint_cap: [u8; 8],
+    pub epoch_duration: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitBusArgs {
+    pub id: u8,
+    pub bump: u8,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct OpenArgs {
@@ -97,6 +161,50 @@ pub struct MineArgs {
 pub struct MineArgsV2 {
     pub digest: [u8; 16],
     pub nonce: [u8; 8],
+    /// Bitmask of the optional accounts which follow the required mine accounts, as a little-endian u32.
+    pub optional_accounts: [u8; 4],
+}
+
+impl MineArgsV2 {
+    pub fn has(&self, account: MineOptionalAccount) -> bool {
+        u32::from_le_bytes(self.optional_accounts) & account as u32 != 0
+    }
+
+    /// Returns whether the bitmask declares an optional account this program does not know.
+    pub fn has_unknown(&self) -> bool {
+        u32::from_le_bytes(self.optional_accounts) & !MineOptionalAccount::MASK != 0
+    }
+}
+
+/// Optional accounts accepted by mine. Accounts must be passed after the required accounts
+/// in the order they are declared here.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MineOptionalAccount {
+    Tool = 1 << 0,
+    GuildMember = 1 << 1,
+    Guild = 1 << 2,
+    MinerStats = 1 << 3,
+    /// The leaderboard of the bus. Requires the miner stats account.
+    Leaderboard = 1 << 4,
+    /// A second bus which pays the part of the reward the primary bus cannot cover.
+    OverflowBus = 1 << 5,
+    /// The CHROMIUM stake of the proof.
+    ChromiumStake = 1 << 6,
+    /// The delegation of the proof. Required when the signer is a delegate rather than the proof's miner.
+    Delegation = 1 << 7,
+}
+
+impl MineOptionalAccount {
+    /// The bitmask of every optional account.
+    pub const MASK: u32 = Self::Tool as u32
+        | Self::GuildMember as u32
+        | Self::Guild as u32
+        | Self::MinerStats as u32
+        | Self::Leaderboard as u32
+        | Self::OverflowBus as u32
+        | Self::ChromiumStake as u32
+        | Self::Delegation as u32;
 }
 
 #[repr(C)]
@@ -105,10 +213,20 @@ pub struct ClaimArgs {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitReprocessArgs {
+    pub reprocessor_bump: u8,
+    pub commitment: [u8; 32],
+    pub keeper: Pubkey,
+    pub keeper_tip: [u8; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct ReprocessArgs {
     pub reprocessor_bump: u8,
+    pub secret: [u8; 32],
 }
 
 #[repr(C)]
@@ -117,6 +235,36 @@ pub struct StakeArgs {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct StakeChromiumArgs {
+    pub bump: u8,
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetDelegateArgs {
+    pub bump: u8,
+    pub delegate: Pubkey,
+    pub expires_at: [u8; 8],
+    pub restrictions: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TransferProofArgs {
+    pub proof_bump: u8,
+    pub miner_stats_bump: u8,
+    pub chromium_stake_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ProposeProofTransferArgs {
+    pub bump: u8,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct UpgradeArgs {
@@ -125,24 +273,46 @@ pub struct UpgradeArgs {
 
 impl_to_bytes!(InitializeArgs);
 impl_to_bytes!(InitChromiumArgs);
+impl_to_bytes!(InitEpochHistoryArgs);
+impl_to_bytes!(InitLeaderboardArgs);
+impl_to_bytes!(SetResourceConfigArgs);
+impl_to_bytes!(SetChromiumConfigArgs);
+impl_to_bytes!(InitBusArgs);
 impl_to_bytes!(OpenArgs);
 impl_to_bytes!(MineArgs);
+impl_to_bytes!(MineArgsV2);
 impl_to_bytes!(ClaimArgs);
 impl_to_bytes!(StakeArgs);
+impl_to_bytes!(StakeChromiumArgs);
+impl_to_bytes!(SetDelegateArgs);
+impl_to_bytes!(TransferProofArgs);
+impl_to_bytes!(ProposeProofTransferArgs);
 impl_to_bytes!(UpgradeArgs);
 impl_to_bytes!(EquipArgs);
 impl_to_bytes!(UnequipArgs);
+impl_to_bytes!(InitReprocessArgs);
 impl_to_bytes!(ReprocessArgs);
 
 impl_instruction_from_bytes!(InitializeArgs);
 impl_instruction_from_bytes!(InitChromiumArgs);
+impl_instruction_from_bytes!(InitEpochHistoryArgs);
+impl_instruction_from_bytes!(InitLeaderboardArgs);
+impl_instruction_from_bytes!(SetResourceConfigArgs);
+impl_instruction_from_bytes!(SetChromiumConfigArgs);
+impl_instruction_from_bytes!(InitBusArgs);
 impl_instruction_from_bytes!(OpenArgs);
 impl_instruction_from_bytes!(MineArgs);
+impl_instruction_from_bytes!(MineArgsV2);
 impl_instruction_from_bytes!(ClaimArgs);
 impl_instruction_from_bytes!(StakeArgs);
+impl_instruction_from_bytes!(StakeChromiumArgs);
+impl_instruction_from_bytes!(SetDelegateArgs);
+impl_instruction_from_bytes!(TransferProofArgs);
+impl_instruction_from_bytes!(ProposeProofTransferArgs);
 impl_instruction_from_bytes!(UpgradeArgs);
 impl_instruction_from_bytes!(EquipArgs);
 impl_instruction_from_bytes!(UnequipArgs);
+impl_instruction_from_bytes!(InitReprocessArgs);
 impl_instruction_from_bytes!(ReprocessArgs);
 
 /// Builds an auth instruction.
@@ -214,12 +384,20 @@ pub fn claim_wood(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instructi
 /// Builds a close instruction.
 pub fn close_coal(signer: Pubkey) -> Instruction {
     let proof_pda = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id());
+    let miner_stats_pda = Pubkey::find_program_address(&[MINER_STATS, proof_pda.0.as_ref()], &crate::id());
+    let proof_transfer_pda = Pubkey::find_program_address(&[PROOF_TRANSFER, proof_pda.0.as_ref()], &crate::id());
+    let delegation_pda = Pubkey::find_program_address(&[DELEGATION, proof_pda.0.as_ref()], &crate::id());
+    let chromium_stake_pda = Pubkey::find_program_address(&[CHROMIUM_STAKE, proof_pda.0.as_ref()], &crate::id());
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(proof_pda.0, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(miner_stats_pda.0, false),
+            AccountMeta::new(proof_transfer_pda.0, false),
+            AccountMeta::new(delegation_pda.0, false),
+            AccountMeta::new(chromium_stake_pda.0, false),
         ],
         data: CoalInstruction::Close.to_vec(),
     }
@@ -227,18 +405,27 @@ pub fn close_coal(signer: Pubkey) -> Instruction {
 
 pub fn close_wood(signer: Pubkey) -> Instruction {
     let proof_pda = Pubkey::find_program_address(&[WOOD_PROOF, signer.as_ref()], &crate::id());
+    let miner_stats_pda = Pubkey::find_program_address(&[MINER_STATS, proof_pda.0.as_ref()], &crate::id());
+    let proof_transfer_pda = Pubkey::find_program_address(&[PROOF_TRANSFER, proof_pda.0.as_ref()], &crate::id());
+    let delegation_pda = Pubkey::find_program_address(&[DELEGATION, proof_pda.0.as_ref()], &crate::id());
+    let chromium_stake_pda = Pubkey::find_program_address(&[CHROMIUM_STAKE, proof_pda.0.as_ref()], &crate::id());
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(proof_pda.0, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(miner_stats_pda.0, false),
+            AccountMeta::new(proof_transfer_pda.0, false),
+            AccountMeta::new(delegation_pda.0, false),
+            AccountMeta::new(chromium_stake_pda.0, false),
         ],
         data: CoalInstruction::Close.to_vec(),
     }
 }
 
 /// Builds a mine instruction.
+#[allow(clippy::too_many_arguments)]
 pub fn mine_coal(
     signer: Pubkey,
     proof_authority: Pubkey,
@@ -246,6 +433,11 @@ pub fn mine_coal(
     tool: Option<Pubkey>,
     member: Option<Pubkey>,
     guild: Option<Pubkey>,
+    miner_stats: bool,
+    leaderboard: bool,
+    overflow_bus: Option<Pubkey>,
+    chromium_stake: bool,
+    delegation: bool,
     solution: Solution,
 ) -> Instruction {
     let proof = Pubkey::find_program_address(&[COAL_PROOF, proof_authority.as_ref()], &crate::id()).0;
@@ -257,20 +449,53 @@ pub fn mine_coal(
         AccountMeta::new(proof, false),
         AccountMeta::new_readonly(sysvar::instructions::id(), false),
         AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new_readonly(COAL_RESOURCE_CONFIG_ADDRESS, false),
     ];
+    let mut optional_accounts = 0u32;
 
     if let Some(tool) = tool {
         accounts.push(AccountMeta::new(tool, false));
+        optional_accounts |= MineOptionalAccount::Tool as u32;
     }
 
     if let Some(member) = member {
         let guild_config = coal_guilds_api::state::config_pda().0;
         accounts.push(AccountMeta::new_readonly(guild_config, false));
         accounts.push(AccountMeta::new_readonly(member, false));
+        optional_accounts |= MineOptionalAccount::GuildMember as u32;
+
+        if let Some(guild) = guild {
+            accounts.push(AccountMeta::new_readonly(guild, false));
+            optional_accounts |= MineOptionalAccount::Guild as u32;
+        }
     }
 
-    if let Some(guild) = guild {
-        accounts.push(AccountMeta::new_readonly(guild, false));
+    if miner_stats {
+        let miner_stats = Pubkey::find_program_address(&[MINER_STATS, proof.as_ref()], &crate::id()).0;
+        accounts.push(AccountMeta::new(miner_stats, false));
+        optional_accounts |= MineOptionalAccount::MinerStats as u32;
+    }
+
+    if leaderboard {
+        accounts.push(AccountMeta::new(leaderboard_pda(bus).0, false));
+        optional_accounts |= MineOptionalAccount::Leaderboard as u32;
+    }
+
+    if let Some(overflow_bus) = overflow_bus {
+        accounts.push(AccountMeta::new(overflow_bus, false));
+        optional_accounts |= MineOptionalAccount::OverflowBus as u32;
+    }
+
+    if chromium_stake {
+        let chromium_stake = Pubkey::find_program_address(&[CHROMIUM_STAKE, proof.as_ref()], &crate::id()).0;
+        accounts.push(AccountMeta::new_readonly(chromium_stake, false));
+        optional_accounts |= MineOptionalAccount::ChromiumStake as u32;
+    }
+
+    if delegation {
+        let delegation = Pubkey::find_program_address(&[DELEGATION, proof.as_ref()], &crate::id()).0;
+        accounts.push(AccountMeta::new_readonly(delegation, false));
+        optional_accounts |= MineOptionalAccount::Delegation as u32;
     }
 
     Instruction {
@@ -278,9 +503,10 @@ pub fn mine_coal(
         accounts,
         data: [
             CoalInstruction::Mine.to_vec(),
-            MineArgs {
+            MineArgsV2 {
                 digest: solution.d,
                 nonce: solution.n,
+                optional_accounts: optional_accounts.to_le_bytes(),
             }
             .to_bytes()
             .to_vec(),
@@ -289,31 +515,74 @@ pub fn mine_coal(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn chop_wood(
     signer: Pubkey,
     proof_authority: Pubkey,
     bus: Pubkey,
+    tool: Option<Pubkey>,
+    miner_stats: bool,
+    leaderboard: bool,
+    overflow_bus: Option<Pubkey>,
+    chromium_stake: bool,
+    delegation: bool,
     solution: Solution,
 ) -> Instruction {
     let proof = Pubkey::find_program_address(&[WOOD_PROOF, proof_authority.as_ref()], &crate::id()).0;
-    let tool = Pubkey::find_program_address(&[WOOD_MAIN_HAND_TOOL, proof_authority.as_ref()], &crate::id()).0;
+
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(bus, false),
+        AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false),
+        AccountMeta::new(proof, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new_readonly(WOOD_RESOURCE_CONFIG_ADDRESS, false),
+    ];
+    let mut optional_accounts = 0u32;
+
+    if let Some(tool) = tool {
+        accounts.push(AccountMeta::new(tool, false));
+        optional_accounts |= MineOptionalAccount::Tool as u32;
+    }
+
+    if miner_stats {
+        let miner_stats = Pubkey::find_program_address(&[MINER_STATS, proof.as_ref()], &crate::id()).0;
+        accounts.push(AccountMeta::new(miner_stats, false));
+        optional_accounts |= MineOptionalAccount::MinerStats as u32;
+    }
+
+    if leaderboard {
+        accounts.push(AccountMeta::new(leaderboard_pda(bus).0, false));
+        optional_accounts |= MineOptionalAccount::Leaderboard as u32;
+    }
+
+    if let Some(overflow_bus) = overflow_bus {
+        accounts.push(AccountMeta::new(overflow_bus, false));
+        optional_accounts |= MineOptionalAccount::OverflowBus as u32;
+    }
+
+    if chromium_stake {
+        let chromium_stake = Pubkey::find_program_address(&[CHROMIUM_STAKE, proof.as_ref()], &crate::id()).0;
+        accounts.push(AccountMeta::new_readonly(chromium_stake, false));
+        optional_accounts |= MineOptionalAccount::ChromiumStake as u32;
+    }
+
+    if delegation {
+        let delegation = Pubkey::find_program_address(&[DELEGATION, proof.as_ref()], &crate::id()).0;
+        accounts.push(AccountMeta::new_readonly(delegation, false));
+        optional_accounts |= MineOptionalAccount::Delegation as u32;
+    }
 
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(bus, false),
-            AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false),
-            AccountMeta::new(proof, false),
-            AccountMeta::new_readonly(sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
-            AccountMeta::new(tool, false),
-        ],
+        accounts,
         data: [
             CoalInstruction::Mine.to_vec(),
-            MineArgs {
+            MineArgsV2 {
                 digest: solution.d,
                 nonce: solution.n,
+                optional_accounts: optional_accounts.to_le_bytes(),
             }
             .to_bytes()
             .to_vec(),
@@ -363,6 +632,25 @@ pub fn open_wood(signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instruction {
     }
 }
 
+/// Builds an open miner stats instruction for a COAL or WOOD proof.
+pub fn open_miner_stats(signer: Pubkey, payer: Pubkey, proof: Pubkey) -> Instruction {
+    let miner_stats_pda = Pubkey::find_program_address(&[MINER_STATS, proof.as_ref()], &crate::id());
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(miner_stats_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::OpenMinerStats.to_vec(),
+            OpenArgs { bump: miner_stats_pda.1 }.to_bytes().to_vec(),
+        ]
+        .concat(),
+    }
+}
 
 /// Builds an equip instruction
 pub fn equip(
@@ -437,57 +725,100 @@ pub fn unequip(
     }
 }
 
-/// Builds a reset instruction.
-pub fn reset_coal(signer: Pubkey) -> Instruction {
+/// Builds a reset all instruction, which resets every resource whose epoch has ended. The instruction
+/// references more accounts than fit in a legacy transaction, so it should be sent with an address lookup table.
+/// Up to `MAX_BUS_COUNT` buses per resource are supported.
+pub fn reset_all(signer: Pubkey, coal_bus_count: usize, wood_bus_count: usize) -> Instruction {
+    let mut accounts = reset_coal(signer, coal_bus_count).accounts;
+    accounts.extend(reset_wood(signer, wood_bus_count).accounts.into_iter().skip(1));
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::ResetAll.to_vec(),
+    }
+}
+
+/// Builds a merge leaderboards instruction, which merges the bus leaderboards of the epoch which just ended
+/// into the leaderboard of the resource.
+pub fn merge_leaderboards(signer: Pubkey, resource: Pubkey, bus_count: usize) -> Instruction {
+    let (config, busses) = if resource.eq(&COAL_MINT_ADDRESS) {
+        (COAL_CONFIG_ADDRESS, &COAL_BUS_ADDRESSES[..bus_count])
+    } else {
+        (WOOD_CONFIG_ADDRESS, &WOOD_BUS_ADDRESSES[..bus_count])
+    };
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(config, false),
+        AccountMeta::new(leaderboard_pda(resource).0, false),
+    ];
+    for bus in busses {
+        accounts.push(AccountMeta::new(leaderboard_pda(*bus).0, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::MergeLeaderboards.to_vec(),
+    }
+}
+
+/// Builds a reset instruction. The reset bounty is paid to the signer's associated token account, which only
+/// has to exist if a bounty is paid.
+pub fn reset_coal(signer: Pubkey, bus_count: usize) -> Instruction {
     let treasury_tokens = spl_associated_token_account::get_associated_token_address(
         &TREASURY_ADDRESS,
         &COAL_MINT_ADDRESS,
     );
+    let bounty_tokens = spl_associated_token_account::get_associated_token_address(&signer, &COAL_MINT_ADDRESS);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(COAL_CONFIG_ADDRESS, false),
+        AccountMeta::new(COAL_MINT_ADDRESS, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(treasury_tokens, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(COAL_EPOCH_HISTORY_ADDRESS, false),
+        AccountMeta::new(COAL_RESOURCE_CONFIG_ADDRESS, false),
+        AccountMeta::new(bounty_tokens, false),
+    ];
+    for bus in &COAL_BUS_ADDRESSES[..bus_count] {
+        accounts.push(AccountMeta::new(*bus, false));
+    }
+
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(COAL_BUS_ADDRESSES[0], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[1], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[2], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[3], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[4], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[5], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[6], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[7], false),
-            AccountMeta::new(COAL_CONFIG_ADDRESS, false),
-            AccountMeta::new(COAL_MINT_ADDRESS, false),
-            AccountMeta::new(TREASURY_ADDRESS, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: CoalInstruction::Reset.to_vec(),
     }
 }
 
-pub fn reset_wood(signer: Pubkey) -> Instruction {
+/// Builds a reset instruction. The reset bounty is paid to the signer's associated token account, which only
+/// has to exist if a bounty is paid.
+pub fn reset_wood(signer: Pubkey, bus_count: usize) -> Instruction {
     let treasury_tokens = spl_associated_token_account::get_associated_token_address(
         &TREASURY_ADDRESS,
         &WOOD_MINT_ADDRESS,
     );
+    let bounty_tokens = spl_associated_token_account::get_associated_token_address(&signer, &WOOD_MINT_ADDRESS);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
+        AccountMeta::new(WOOD_MINT_ADDRESS, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(treasury_tokens, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(WOOD_EPOCH_HISTORY_ADDRESS, false),
+        AccountMeta::new(WOOD_RESOURCE_CONFIG_ADDRESS, false),
+        AccountMeta::new(bounty_tokens, false),
+    ];
+    for bus in &WOOD_BUS_ADDRESSES[..bus_count] {
+        accounts.push(AccountMeta::new(*bus, false));
+    }
+
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[0], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[1], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[2], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[3], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[4], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[5], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[6], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[7], false),
-            AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
-            AccountMeta::new(WOOD_MINT_ADDRESS, false),
-            AccountMeta::new(TREASURY_ADDRESS, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: CoalInstruction::Reset.to_vec(),
     }
 }
@@ -547,6 +878,87 @@ pub fn stake_wood(signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
     }
 }
 
+/// Builds a stake CHROMIUM instruction, which stakes CHROMIUM against the proof of the given resource.
+pub fn stake_chromium(signer: Pubkey, resource: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_PROOF } else { COAL_PROOF };
+    let proof = Pubkey::find_program_address(&[seed, signer.as_ref()], &crate::id()).0;
+    let chromium_stake_pda = Pubkey::find_program_address(&[CHROMIUM_STAKE, proof.as_ref()], &crate::id());
+    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
+        &TREASURY_ADDRESS,
+        &CHROMIUM_MINT_ADDRESS,
+    );
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(chromium_stake_pda.0, false),
+            AccountMeta::new(sender, false),
+            AccountMeta::new(treasury_tokens, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::StakeChromium.to_vec(),
+            StakeChromiumArgs {
+                bump: chromium_stake_pda.1,
+                amount: amount.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an unstake CHROMIUM instruction, which starts the cooldown of the unstaked CHROMIUM.
+pub fn unstake_chromium(signer: Pubkey, resource: Pubkey, amount: u64) -> Instruction {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_PROOF } else { COAL_PROOF };
+    let proof = Pubkey::find_program_address(&[seed, signer.as_ref()], &crate::id()).0;
+    let chromium_stake = Pubkey::find_program_address(&[CHROMIUM_STAKE, proof.as_ref()], &crate::id()).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(chromium_stake, false),
+        ],
+        data: [
+            CoalInstruction::UnstakeChromium.to_vec(),
+            StakeArgs {
+                amount: amount.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a withdraw CHROMIUM instruction, which withdraws the unstaked CHROMIUM once the cooldown has ended.
+pub fn withdraw_chromium(signer: Pubkey, resource: Pubkey, beneficiary: Pubkey) -> Instruction {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_PROOF } else { COAL_PROOF };
+    let proof = Pubkey::find_program_address(&[seed, signer.as_ref()], &crate::id()).0;
+    let chromium_stake = Pubkey::find_program_address(&[CHROMIUM_STAKE, proof.as_ref()], &crate::id()).0;
+    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
+        &TREASURY_ADDRESS,
+        &CHROMIUM_MINT_ADDRESS,
+    );
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(chromium_stake, false),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new(treasury_tokens, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: CoalInstruction::WithdrawChromium.to_vec(),
+    }
+}
+
 // Build an update instruction.
 pub fn update_coal(signer: Pubkey, miner: Pubkey) -> Instruction {
     let proof = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id()).0;
@@ -574,6 +986,154 @@ pub fn update_wood(signer: Pubkey, miner: Pubkey) -> Instruction {
     }
 }
 
+/// Builds a set delegate instruction, which allows the delegate to mine with the proof of the given resource
+/// until it expires. An expiry in the past revokes the delegate.
+pub fn set_delegate(
+    signer: Pubkey,
+    resource: Pubkey,
+    delegate: Pubkey,
+    expires_at: i64,
+    restrictions: u64,
+) -> Instruction {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_PROOF } else { COAL_PROOF };
+    let proof = Pubkey::find_program_address(&[seed, signer.as_ref()], &crate::id()).0;
+    let delegation_pda = Pubkey::find_program_address(&[DELEGATION, proof.as_ref()], &crate::id());
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(delegation_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::SetDelegate.to_vec(),
+            SetDelegateArgs {
+                bump: delegation_pda.1,
+                delegate,
+                expires_at: expires_at.to_le_bytes(),
+                restrictions: restrictions.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Returns the accounts migrated by a proof transfer, and the args to create them.
+fn transfer_proof_accounts(
+    authority: Pubkey,
+    new_authority: Pubkey,
+    resource: Pubkey,
+) -> (Vec<AccountMeta>, TransferProofArgs) {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_PROOF } else { COAL_PROOF };
+    let proof = Pubkey::find_program_address(&[seed, authority.as_ref()], &crate::id()).0;
+    let new_proof_pda = Pubkey::find_program_address(&[seed, new_authority.as_ref()], &crate::id());
+    let miner_stats = Pubkey::find_program_address(&[MINER_STATS, proof.as_ref()], &crate::id()).0;
+    let new_miner_stats_pda =
+        Pubkey::find_program_address(&[MINER_STATS, new_proof_pda.0.as_ref()], &crate::id());
+    let chromium_stake = Pubkey::find_program_address(&[CHROMIUM_STAKE, proof.as_ref()], &crate::id()).0;
+    let new_chromium_stake_pda =
+        Pubkey::find_program_address(&[CHROMIUM_STAKE, new_proof_pda.0.as_ref()], &crate::id());
+    let proof_transfer = Pubkey::find_program_address(&[PROOF_TRANSFER, proof.as_ref()], &crate::id()).0;
+    let delegation = Pubkey::find_program_address(&[DELEGATION, proof.as_ref()], &crate::id()).0;
+    let reprocessor = Pubkey::find_program_address(&[REPROCESSOR, authority.as_ref()], &crate::id()).0;
+    let accounts = vec![
+        AccountMeta::new(proof, false),
+        AccountMeta::new(new_proof_pda.0, false),
+        AccountMeta::new(miner_stats, false),
+        AccountMeta::new(new_miner_stats_pda.0, false),
+        AccountMeta::new(chromium_stake, false),
+        AccountMeta::new(new_chromium_stake_pda.0, false),
+        AccountMeta::new(proof_transfer, false),
+        AccountMeta::new(delegation, false),
+        AccountMeta::new_readonly(reprocessor, false),
+    ];
+    let args = TransferProofArgs {
+        proof_bump: new_proof_pda.1,
+        miner_stats_bump: new_miner_stats_pda.1,
+        chromium_stake_bump: new_chromium_stake_pda.1,
+    };
+    (accounts, args)
+}
+
+/// Builds a transfer proof instruction, which immediately moves the proof of the given resource, its miner
+/// stats and its CHROMIUM stake to the proof address of the new authority.
+pub fn transfer_proof(signer: Pubkey, resource: Pubkey, new_authority: Pubkey) -> Instruction {
+    let (transfer_accounts, args) = transfer_proof_accounts(signer, new_authority, resource);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(new_authority, false),
+    ];
+    accounts.extend(transfer_accounts);
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: [CoalInstruction::TransferProof.to_vec(), args.to_bytes().to_vec()].concat(),
+    }
+}
+
+/// Builds a propose proof transfer instruction. The transfer is executed once the new authority accepts it.
+pub fn propose_proof_transfer(signer: Pubkey, resource: Pubkey, new_authority: Pubkey) -> Instruction {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_PROOF } else { COAL_PROOF };
+    let proof = Pubkey::find_program_address(&[seed, signer.as_ref()], &crate::id()).0;
+    let proof_transfer_pda = Pubkey::find_program_address(&[PROOF_TRANSFER, proof.as_ref()], &crate::id());
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(new_authority, false),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(proof_transfer_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::ProposeProofTransfer.to_vec(),
+            ProposeProofTransferArgs {
+                bump: proof_transfer_pda.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an accept proof transfer instruction, which executes the transfer proposed by the authority.
+pub fn accept_proof_transfer(signer: Pubkey, authority: Pubkey, resource: Pubkey) -> Instruction {
+    let (transfer_accounts, args) = transfer_proof_accounts(authority, signer, resource);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(authority, false),
+    ];
+    accounts.extend(transfer_accounts);
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: [CoalInstruction::AcceptProofTransfer.to_vec(), args.to_bytes().to_vec()].concat(),
+    }
+}
+
+/// Builds a cancel proof transfer instruction, which withdraws the transfer of the proof of the given resource
+/// proposed by the signer.
+pub fn cancel_proof_transfer(signer: Pubkey, resource: Pubkey) -> Instruction {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_PROOF } else { COAL_PROOF };
+    let proof = Pubkey::find_program_address(&[seed, signer.as_ref()], &crate::id()).0;
+    let proof_transfer = Pubkey::find_program_address(&[PROOF_TRANSFER, proof.as_ref()], &crate::id()).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(proof_transfer, false),
+        ],
+        data: CoalInstruction::CancelProofTransfer.to_vec(),
+    }
+}
+
 pub fn init_chromium(signer: Pubkey) -> Instruction {
     let mint_pda = Pubkey::find_program_address(&[CHROMIUM_MINT, MINT_NOISE.as_slice()], &crate::id());
     let metadata_pda = Pubkey::find_program_address(
@@ -616,7 +1176,195 @@ pub fn init_chromium(signer: Pubkey) -> Instruction {
     }
 }
 
-pub fn init_reprocess(signer: Pubkey) -> Instruction {
+/// Builds an init epoch history instruction.
+pub fn init_epoch_history(signer: Pubkey, resource: Pubkey) -> Instruction {
+    let epoch_history_pda = Pubkey::find_program_address(&[EPOCH_HISTORY, resource.as_ref()], &crate::id());
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(resource, false),
+            AccountMeta::new(epoch_history_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::InitEpochHistory.to_vec(),
+            InitEpochHistoryArgs {
+                bump: epoch_history_pda.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an init leaderboard instruction. The leaderboard belongs to the given bus, or to the
+/// resource itself if no bus is given.
+pub fn init_leaderboard(signer: Pubkey, resource: Pubkey, bus: Option<Pubkey>) -> Instruction {
+    let leaderboard_pda = leaderboard_pda(bus.unwrap_or(resource));
+
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(resource, false),
+        AccountMeta::new(leaderboard_pda.0, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if let Some(bus) = bus {
+        accounts.push(AccountMeta::new_readonly(bus, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: [
+            CoalInstruction::InitLeaderboard.to_vec(),
+            InitLeaderboardArgs {
+                bump: leaderboard_pda.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a set resource config instruction. The config account is created if it does not exist yet. The
+/// buses in use must exist, so they are passed for validation.
+#[allow(clippy::too_many_arguments)]
+pub fn set_resource_config(
+    signer: Pubkey,
+    resource: Pubkey,
+    emission_model: EmissionModelKind,
+    max_supply: u64,
+    min_bus_epoch_rewards: u64,
+    max_bus_epoch_rewards: u64,
+    epoch_rewards_decay: u64,
+    epoch_duration: i64,
+    bus_count: u64,
+    reset_bounty: u64,
+    halving_count: u64,
+) -> Instruction {
+    let resource_config_pda = Pubkey::find_program_address(&[RESOURCE_CONFIG, resource.as_ref()], &crate::id());
+    let (config, bus_addresses) = if resource.eq(&COAL_MINT_ADDRESS) {
+        (COAL_CONFIG_ADDRESS, &COAL_BUS_ADDRESSES)
+    } else {
+        (WOOD_CONFIG_ADDRESS, &WOOD_BUS_ADDRESSES)
+    };
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(resource, false),
+        AccountMeta::new(resource_config_pda.0, false),
+        AccountMeta::new(config, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for bus in bus_addresses.iter().take(bus_count as usize) {
+        accounts.push(AccountMeta::new_readonly(*bus, false));
+    }
+
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: [
+            CoalInstruction::SetResourceConfig.to_vec(),
+            SetResourceConfigArgs {
+                bump: resource_config_pda.1,
+                emission_model: emission_model.into(),
+                max_supply: max_supply.to_le_bytes(),
+                min_bus_epoch_rewards: min_bus_epoch_rewards.to_le_bytes(),
+                max_bus_epoch_rewards: max_bus_epoch_rewards.to_le_bytes(),
+                epoch_rewards_decay: epoch_rewards_decay.to_le_bytes(),
+                epoch_duration: epoch_duration.to_le_bytes(),
+                bus_count: bus_count.to_le_bytes(),
+                reset_bounty: reset_bounty.to_le_bytes(),
+                halving_count: halving_count.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a set CHROMIUM config instruction. The config account is created if it does not exist yet.
+pub fn set_chromium_config(signer: Pubkey, max_supply: u64, epoch_mint_cap: u64, epoch_duration: i64) -> Instruction {
+    let chromium_config_pda = Pubkey::find_program_address(&[CHROMIUM_CONFIG], &crate::id());
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(chromium_config_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::SetChromiumConfig.to_vec(),
+            SetChromiumConfigArgs {
+                bump: chromium_config_pda.1,
+                max_supply: max_supply.to_le_bytes(),
+                epoch_mint_cap: epoch_mint_cap.to_le_bytes(),
+                epoch_duration: epoch_duration.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an init bus instruction.
+pub fn init_bus(signer: Pubkey, resource: Pubkey, id: u8) -> Instruction {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_BUS } else { COAL_BUS };
+    let bus_pda = Pubkey::find_program_address(&[seed, &[id]], &crate::id());
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(resource, false),
+            AccountMeta::new(bus_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::InitBus.to_vec(),
+            InitBusArgs {
+                id,
+                bump: bus_pda.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a withdraw fees instruction, which sends the unwithdrawn reprocess fees to the beneficiary.
+pub fn withdraw_fees(signer: Pubkey, beneficiary: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: CoalInstruction::WithdrawFees.to_vec(),
+    }
+}
+
+/// Builds an init reprocess instruction for the proof of the given resource. The secret must be kept until the
+/// reprocess is finalized, since only its hash is committed on chain. If a keeper is given, the secret must
+/// also be shared with it so it can finalize on the signer's behalf in exchange for the tip.
+pub fn init_reprocess(
+    signer: Pubkey,
+    resource: Pubkey,
+    secret: [u8; 32],
+    keeper: Option<Pubkey>,
+    keeper_tip: u64,
+) -> Instruction {
+    let proof = reprocess_proof_address(signer, resource);
+    let miner_stats = Pubkey::find_program_address(&[MINER_STATS, proof.as_ref()], &crate::id()).0;
     let (reprocessor, reprocessor_bump) = Pubkey::find_program_address(&[REPROCESSOR, signer.as_ref()], &crate::id());
 
     Instruction {
@@ -625,13 +1373,17 @@ pub fn init_reprocess(signer: Pubkey) -> Instruction {
             AccountMeta::new(signer, true),
             AccountMeta::new(TREASURY_ADDRESS, false),
             AccountMeta::new(reprocessor, false),
-            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
             AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new_readonly(miner_stats, false),
         ],
         data: [
             CoalInstruction::InitReprocess.to_vec(),
-            ReprocessArgs {
+            InitReprocessArgs {
                 reprocessor_bump,
+                commitment: keccak::hashv(&[&secret]).0,
+                keeper: keeper.unwrap_or_default(),
+                keeper_tip: keeper.map_or(0, |_| keeper_tip).to_le_bytes(),
             }
             .to_bytes()
             .to_vec(),
@@ -640,19 +1392,24 @@ pub fn init_reprocess(signer: Pubkey) -> Instruction {
     }
 }
 
-pub fn reprocess(signer: Pubkey) -> Instruction {
-    let (proof, _proof_bump) = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id());
-    let (reprocessor, reprocessor_bump) = Pubkey::find_program_address(&[REPROCESSOR, signer.as_ref()], &crate::id());
-    let (bus, _bus_bump) = Pubkey::find_program_address(&[COAL_BUS, &[0]], &crate::id());
-    let tokens = spl_associated_token_account::get_associated_token_address(&signer, &CHROMIUM_MINT_ADDRESS);
+/// Builds a reprocess instruction for the proof of the given resource, which reveals the secret committed
+/// by the init reprocess instruction. The signer is either the authority or the keeper of the reprocess.
+/// The CHROMIUM is always minted to the authority.
+pub fn reprocess(signer: Pubkey, authority: Pubkey, resource: Pubkey, secret: [u8; 32]) -> Instruction {
+    let proof = reprocess_proof_address(authority, resource);
+    let miner_stats = Pubkey::find_program_address(&[MINER_STATS, proof.as_ref()], &crate::id()).0;
+    let (reprocessor, reprocessor_bump) = Pubkey::find_program_address(&[REPROCESSOR, authority.as_ref()], &crate::id());
+    let tokens = spl_associated_token_account::get_associated_token_address(&authority, &CHROMIUM_MINT_ADDRESS);
     
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
+            AccountMeta::new(authority, false),
             AccountMeta::new(reprocessor, false),
-            AccountMeta::new(proof, false),
-            AccountMeta::new(bus, false),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(miner_stats, false),
+            AccountMeta::new(CHROMIUM_CONFIG_ADDRESS, false),
             AccountMeta::new(CHROMIUM_MINT_ADDRESS, false),
             AccountMeta::new(tokens, false),
             AccountMeta::new_readonly(TREASURY_ADDRESS, false),
@@ -663,10 +1420,37 @@ pub fn reprocess(signer: Pubkey) -> Instruction {
             CoalInstruction::FinalizeReprocess.to_vec(),
             ReprocessArgs {
                 reprocessor_bump,
+                secret,
             }
             .to_bytes()
             .to_vec(),
         ]
         .concat(),
     }
-}
\ No newline at end of file
+}
+
+/// Builds a cancel reprocess instruction for the proof of the given resource, which must be the proof the
+/// reprocess was initialized for. The reprocess fee is not refunded, and cancelling after the target slot
+/// consumes the unreprocessed stats of the proof.
+pub fn cancel_reprocess(signer: Pubkey, resource: Pubkey) -> Instruction {
+    let proof = reprocess_proof_address(signer, resource);
+    let miner_stats = Pubkey::find_program_address(&[MINER_STATS, proof.as_ref()], &crate::id()).0;
+    let reprocessor = Pubkey::find_program_address(&[REPROCESSOR, signer.as_ref()], &crate::id()).0;
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(reprocessor, false),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(miner_stats, false),
+        ],
+        data: CoalInstruction::CancelReprocess.to_vec(),
+    }
+}
+
+/// Returns the address of the proof of the given resource which a reprocess applies to.
+fn reprocess_proof_address(authority: Pubkey, resource: Pubkey) -> Pubkey {
+    let seed = if resource.eq(&WOOD_MINT_ADDRESS) { WOOD_PROOF } else { COAL_PROOF };
+    Pubkey::find_program_address(&[seed, authority.as_ref()], &crate::id()).0
+}
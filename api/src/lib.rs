@@ -1,4 +1,6 @@
 pub mod consts;
+pub mod difficulty;
+pub mod emission;
 pub mod error;
 pub mod event;
 pub mod instruction;
@@ -12,7 +12,7 @@ use mpl_core::{Asset, types::UpdateAuthority};
 
 use crate::{
     consts::*,
-    state::{Bus, Config, Proof, ProofV2, Reprocessor, Tool, Treasury, WoodConfig, WoodTool},
+    state::{Bus, ChromiumConfig, ChromiumStake, Config, Delegation, EpochHistory, Leaderboard, MinerStats, Proof, ProofTransfer, ProofV2, Reprocessor, ResourceConfig, Tool, Treasury, WoodConfig, WoodTool},
     utils::{AccountDeserialize, Discriminator},
 };
 
@@ -236,6 +236,266 @@ pub fn load_wood_config<'a, 'info>(
     Ok(())
 }
 
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into an epoch history account.
+/// - Epoch history resource does not match the expected resource.
+/// - Expected to be writable, but is not.
+pub fn load_epoch_history(
+    info: &AccountInfo<'_>,
+    resource: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let epoch_history_data = info.data.borrow();
+    let epoch_history = EpochHistory::try_from_bytes(&epoch_history_data)?;
+
+    if epoch_history.resource.ne(resource) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a leaderboard account.
+/// - Leaderboard resource or bus does not match the expected values.
+/// - Expected to be writable, but is not.
+pub fn load_leaderboard(
+    info: &AccountInfo<'_>,
+    resource: &Pubkey,
+    bus: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let leaderboard_data = info.data.borrow();
+    let leaderboard = Leaderboard::try_from_bytes(&leaderboard_data)?;
+
+    if leaderboard.resource.ne(resource) || leaderboard.bus.ne(bus) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a resource config account.
+/// - Resource config resource does not match the expected resource.
+/// - Expected to be writable, but is not.
+pub fn load_resource_config(
+    info: &AccountInfo<'_>,
+    resource: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let resource_config_data = info.data.borrow();
+    let resource_config = ResourceConfig::try_from_bytes(&resource_config_data)?;
+
+    if resource_config.resource.ne(resource) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Address does not match the expected address.
+/// - Data is empty.
+/// - Data cannot deserialize into a CHROMIUM config account.
+/// - Expected to be writable, but is not.
+pub fn load_chromium_config(info: &AccountInfo<'_>, is_writable: bool) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.key.ne(&CHROMIUM_CONFIG_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    ChromiumConfig::try_from_bytes(&info.data.borrow())?;
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a CHROMIUM stake account.
+/// - CHROMIUM stake proof does not match the expected proof.
+/// - Expected to be writable, but is not.
+pub fn load_chromium_stake(
+    info: &AccountInfo<'_>,
+    proof: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let chromium_stake_data = info.data.borrow();
+    let chromium_stake = ChromiumStake::try_from_bytes(&chromium_stake_data)?;
+
+    if chromium_stake.proof.ne(proof) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a delegation account.
+/// - Delegation proof does not match the expected proof.
+/// - Expected to be writable, but is not.
+pub fn load_delegation(
+    info: &AccountInfo<'_>,
+    proof: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let delegation_data = info.data.borrow();
+    let delegation = Delegation::try_from_bytes(&delegation_data)?;
+
+    if delegation.proof.ne(proof) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a proof transfer account.
+/// - Proof transfer proof does not match the expected proof.
+/// - Expected to be writable, but is not.
+pub fn load_proof_transfer(
+    info: &AccountInfo<'_>,
+    proof: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let proof_transfer_data = info.data.borrow();
+    let proof_transfer = ProofTransfer::try_from_bytes(&proof_transfer_data)?;
+
+    if proof_transfer.proof.ne(proof) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a miner stats account.
+/// - Miner stats proof does not match the expected proof.
+/// - Expected to be writable, but is not.
+pub fn load_miner_stats(
+    info: &AccountInfo<'_>,
+    proof: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let miner_stats_data = info.data.borrow();
+    let miner_stats = MinerStats::try_from_bytes(&miner_stats_data)?;
+
+    if miner_stats.proof.ne(proof) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
 /// Errors if:
 /// - Owner is not Coal program.
 /// - Data is empty.
@@ -411,6 +671,43 @@ pub fn load_proof_v2_with_miner<'a, 'info>(
     Ok(())
 }
 
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a proof or proof v2 account.
+/// - Proof authority does not match the expected address.
+/// - Expected to be writable, but is not.
+pub fn load_any_proof_with_authority(
+    info: &AccountInfo<'_>,
+    authority: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let proof_data = info.data.borrow();
+    let proof_authority = if proof_data[0].eq(&Proof::discriminator()) {
+        Proof::try_from_bytes(&proof_data)?.authority
+    } else {
+        ProofV2::try_from_bytes(&proof_data)?.authority
+    };
+
+    if proof_authority.ne(authority) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
 /// Errors if:
 /// - Owner is not Coal program.
 /// - Data is empty.
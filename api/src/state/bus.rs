@@ -4,8 +4,8 @@ use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
 
 use super::AccountDiscriminator;
 
-/// Bus accounts are responsible for distributing mining rewards. There are 8 busses total
-/// to minimize write-lock contention and allow Solana to process mine instructions in parallel.
+/// Bus accounts are responsible for distributing mining rewards. There are 8 busses by default, configurable
+/// per resource, to minimize write-lock contention and allow Solana to process mine instructions in parallel.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct Bus {
@@ -31,3 +31,32 @@ impl Discriminator for Bus {
 
 impl_to_bytes!(Bus);
 impl_account_from_bytes!(Bus);
+
+/// Returns the bus a miner should submit their next hash to. This is the bus with the most rewards left,
+/// since it is the least likely to run out before the hash lands. Ties go to the lowest ID.
+pub fn best_bus(busses: &[Bus]) -> Option<&Bus> {
+    busses
+        .iter()
+        .max_by(|a, b| a.rewards.cmp(&b.rewards).then(b.id.cmp(&a.id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bus(id: u64, rewards: u64) -> Bus {
+        Bus {
+            id,
+            rewards,
+            theoretical_rewards: 0,
+            top_balance: 0,
+        }
+    }
+
+    #[test]
+    fn test_best_bus() {
+        let busses = [bus(0, 100), bus(1, 300), bus(2, 300), bus(3, 0)];
+        assert_eq!(best_bus(&busses).map(|bus| bus.id), Some(1));
+        assert_eq!(best_bus(&[]), None);
+    }
+}
@@ -0,0 +1,153 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
+
+use super::AccountDiscriminator;
+
+/// ChromiumConfig is a singleton account which limits the CHROMIUM minted by reprocessing.
+///
+/// Reprocesses are finalized one at a time, so the demand of an epoch is not known until it ends. When the
+/// demand of the previous epoch exceeded the epoch mint cap, every reward of the current epoch is scaled down
+/// pro-rata by the same factor.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ChromiumConfig {
+    /// The maximum CHROMIUM supply.
+    pub max_supply: u64,
+
+    /// The maximum quantity of CHROMIUM minted per epoch.
+    pub epoch_mint_cap: u64,
+
+    /// The duration of an epoch, in seconds.
+    pub epoch_duration: i64,
+
+    /// The timestamp the current epoch started at.
+    pub epoch_start_at: i64,
+
+    /// The CHROMIUM minted in the current epoch.
+    pub epoch_minted: u64,
+
+    /// The CHROMIUM requested in the current epoch, before scaling.
+    pub epoch_requested: u64,
+
+    /// The CHROMIUM requested in the previous epoch, before scaling.
+    pub prev_epoch_requested: u64,
+}
+
+impl ChromiumConfig {
+    /// Starts a new epoch if the current one has ended. The demand of the ended epoch is only carried over
+    /// if it immediately precedes the new epoch.
+    pub fn rotate(&mut self, now: i64) {
+        let epoch_end_at = self.epoch_start_at.saturating_add(self.epoch_duration);
+        if now.lt(&epoch_end_at) {
+            return;
+        }
+        self.prev_epoch_requested = if now.lt(&epoch_end_at.saturating_add(self.epoch_duration)) {
+            self.epoch_requested
+        } else {
+            0
+        };
+        self.epoch_start_at = now;
+        self.epoch_minted = 0;
+        self.epoch_requested = 0;
+    }
+
+    /// Returns a requested reward scaled pro-rata by the demand of the previous epoch.
+    pub fn scale(&self, reward: u64) -> u64 {
+        if self.prev_epoch_requested.gt(&self.epoch_mint_cap) {
+            (reward as u128)
+                .saturating_mul(self.epoch_mint_cap as u128)
+                .saturating_div(self.prev_epoch_requested as u128) as u64
+        } else {
+            reward
+        }
+    }
+
+    /// Returns the quantity which can still be minted in the current epoch, given the supply. It is limited by
+    /// what is left of the epoch mint cap and the max supply.
+    pub fn available(&self, supply: u64) -> u64 {
+        self.epoch_mint_cap
+            .saturating_sub(self.epoch_minted)
+            .min(self.max_supply.saturating_sub(supply))
+    }
+
+    /// Records a requested reward and returns the quantity to mint. The reward is scaled pro-rata by the
+    /// demand of the previous epoch, and limited by what is available.
+    pub fn mint(&mut self, reward: u64, supply: u64) -> u64 {
+        let amount = self.scale(reward).min(self.available(supply));
+        self.epoch_requested = self.epoch_requested.saturating_add(reward);
+        self.epoch_minted = self.epoch_minted.saturating_add(amount);
+        amount
+    }
+}
+
+impl Discriminator for ChromiumConfig {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::ChromiumConfig.into()
+    }
+}
+
+impl_to_bytes!(ChromiumConfig);
+impl_account_from_bytes!(ChromiumConfig);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::ChromiumConfig;
+
+    fn config() -> ChromiumConfig {
+        let mut config = ChromiumConfig::zeroed();
+        config.max_supply = 10_000;
+        config.epoch_mint_cap = 1000;
+        config.epoch_duration = 60;
+        config
+    }
+
+    #[test]
+    fn test_mint_cap() {
+        let mut config = config();
+        assert_eq!(config.mint(600, 0), 600);
+        assert_eq!(config.mint(600, 600), 400);
+        assert_eq!(config.mint(600, 1000), 0);
+        assert_eq!(config.epoch_requested, 1800);
+        assert_eq!(config.epoch_minted, 1000);
+    }
+
+    #[test]
+    fn test_mint_max_supply() {
+        let mut config = config();
+        assert_eq!(config.mint(600, 9_900), 100);
+        assert_eq!(config.mint(600, 10_000), 0);
+    }
+
+    #[test]
+    fn test_available() {
+        let mut config = config();
+        assert_eq!(config.available(0), 1000);
+        assert_eq!(config.available(9_500), 500);
+        config.mint(800, 0);
+        assert_eq!(config.available(800), 200);
+        config.mint(800, 800);
+        assert_eq!(config.available(1000), 0);
+    }
+
+    #[test]
+    fn test_rotate_pro_rata() {
+        let mut config = config();
+        config.mint(1000, 0);
+        config.mint(1000, 1000);
+        config.rotate(59);
+        assert_eq!(config.epoch_minted, 1000);
+
+        // Demand of the previous epoch was twice the cap, so rewards are halved.
+        config.rotate(60);
+        assert_eq!(config.prev_epoch_requested, 2000);
+        assert_eq!(config.mint(500, 1000), 250);
+
+        // Demand is not carried over across idle epochs.
+        config.rotate(300);
+        assert_eq!(config.prev_epoch_requested, 0);
+        assert_eq!(config.mint(500, 1250), 500);
+    }
+}
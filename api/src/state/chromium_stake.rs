@@ -0,0 +1,95 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    consts::*,
+    utils::{impl_account_from_bytes, impl_to_bytes, Discriminator},
+};
+
+use super::{AccountDiscriminator, BPS_DENOMINATOR};
+
+/// ChromiumStake is an account (1 per proof) which holds the CHROMIUM staked to boost the proof's mining
+/// rewards. The staked CHROMIUM is held by the treasury.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ChromiumStake {
+    /// The proof this stake boosts.
+    pub proof: Pubkey,
+
+    /// The quantity of CHROMIUM staked.
+    pub balance: u64,
+
+    /// The last time CHROMIUM was staked.
+    pub last_stake_at: i64,
+
+    /// The quantity of CHROMIUM unstaked and waiting for the cooldown to end.
+    pub unstaking_balance: u64,
+
+    /// The time the unstaking balance can be withdrawn at.
+    pub withdrawable_at: i64,
+}
+
+impl ChromiumStake {
+    /// Returns the bonus reward earned on top of the given reward. The bonus grows linearly with the staked
+    /// balance up to `MAX_CHROMIUM_STAKE_MULTIPLIER` at `CHROMIUM_STAKE_TARGET`. Like COAL stake, the bonus
+    /// is only active if the last stake was more than one minute ago to protect against flash loan attacks.
+    pub fn reward(&self, reward: u64, now: i64) -> u64 {
+        if self.last_stake_at.saturating_add(ONE_MINUTE).ge(&now) {
+            return 0;
+        }
+        (reward as u128)
+            .saturating_mul(self.balance.min(CHROMIUM_STAKE_TARGET) as u128)
+            .saturating_mul(MAX_CHROMIUM_STAKE_MULTIPLIER as u128)
+            .saturating_div(CHROMIUM_STAKE_TARGET as u128)
+            .saturating_div(BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Moves the given quantity from the staked balance to the unstaking balance, and restarts the cooldown.
+    pub fn unstake(&mut self, amount: u64, now: i64) {
+        self.balance = self.balance.saturating_sub(amount);
+        self.unstaking_balance = self.unstaking_balance.saturating_add(amount);
+        self.withdrawable_at = now.saturating_add(CHROMIUM_UNSTAKE_COOLDOWN);
+    }
+}
+
+impl Discriminator for ChromiumStake {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::ChromiumStake.into()
+    }
+}
+
+impl_to_bytes!(ChromiumStake);
+impl_account_from_bytes!(ChromiumStake);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use crate::consts::*;
+
+    use super::ChromiumStake;
+
+    #[test]
+    fn test_reward() {
+        let mut stake = ChromiumStake::zeroed();
+        assert_eq!(stake.reward(1000, 120), 0);
+        stake.balance = CHROMIUM_STAKE_TARGET / 2;
+        assert_eq!(stake.reward(1000, 120), 1000 * MAX_CHROMIUM_STAKE_MULTIPLIER / 20_000);
+        // The bonus is bounded by the stake target.
+        stake.balance = CHROMIUM_STAKE_TARGET * 10;
+        assert_eq!(stake.reward(1000, 120), 1000 * MAX_CHROMIUM_STAKE_MULTIPLIER / 10_000);
+        // The bonus is inactive right after staking.
+        stake.last_stake_at = 100;
+        assert_eq!(stake.reward(1000, 120), 0);
+    }
+
+    #[test]
+    fn test_unstake() {
+        let mut stake = ChromiumStake::zeroed();
+        stake.balance = 100;
+        stake.unstake(40, 1000);
+        assert_eq!(stake.balance, 60);
+        assert_eq!(stake.unstaking_balance, 40);
+        assert_eq!(stake.withdrawable_at, 1000 + CHROMIUM_UNSTAKE_COOLDOWN);
+    }
+}
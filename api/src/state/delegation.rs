@@ -0,0 +1,139 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
+
+use super::AccountDiscriminator;
+
+/// The maximum number of delegates per proof.
+pub const MAX_DELEGATES: usize = 8;
+
+/// Delegation is an account (1 per proof) which holds the session keys allowed to mine with the proof,
+/// in addition to the proof's miner. The keys are managed by the proof authority.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Delegation {
+    /// The proof the delegates can mine with.
+    pub proof: Pubkey,
+
+    /// The delegates. Free slots hold the default address.
+    pub delegates: [Delegate; MAX_DELEGATES],
+}
+
+/// A session key allowed to mine with a proof until it expires. Delegates can only mine.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Delegate {
+    /// The delegate key.
+    pub key: Pubkey,
+
+    /// The time the delegate expires at.
+    pub expires_at: i64,
+
+    /// The restrictions of the delegate, as a bitmask of `DelegateRestriction`.
+    pub restrictions: u64,
+}
+
+/// Restrictions which can be applied to a delegate.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DelegateRestriction {
+    /// The delegate cannot mine with a tool.
+    NoTool = 1 << 0,
+}
+
+impl DelegateRestriction {
+    /// The bitmask of every restriction.
+    pub const MASK: u64 = Self::NoTool as u64;
+}
+
+impl Delegate {
+    pub fn is_restricted(&self, restriction: DelegateRestriction) -> bool {
+        self.restrictions & restriction as u64 != 0
+    }
+}
+
+impl Delegation {
+    /// Returns the delegate with the given key, if it has not expired.
+    pub fn find(&self, key: &Pubkey, now: i64) -> Option<&Delegate> {
+        self.delegates
+            .iter()
+            .find(|d| d.key.ne(&Pubkey::default()) && d.key.eq(key) && d.expires_at.gt(&now))
+    }
+
+    /// Adds or updates a delegate and returns whether it was set. A new delegate takes a free slot, or the
+    /// slot of an expired delegate if none is free.
+    pub fn set(&mut self, key: Pubkey, expires_at: i64, restrictions: u64, now: i64) -> bool {
+        let index = self
+            .delegates
+            .iter()
+            .position(|d| d.key.eq(&key))
+            .or_else(|| self.delegates.iter().position(|d| d.key.eq(&Pubkey::default())))
+            .or_else(|| self.delegates.iter().position(|d| d.expires_at.le(&now)));
+        let Some(index) = index else {
+            return false;
+        };
+        self.delegates[index] = Delegate {
+            key,
+            expires_at,
+            restrictions,
+        };
+        true
+    }
+
+    /// Removes the delegate with the given key, if any.
+    pub fn remove(&mut self, key: &Pubkey) {
+        for delegate in self.delegates.iter_mut().filter(|d| d.key.eq(key)) {
+            *delegate = Delegate::zeroed();
+        }
+    }
+}
+
+impl Discriminator for Delegation {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::Delegation.into()
+    }
+}
+
+impl_to_bytes!(Delegation);
+impl_account_from_bytes!(Delegation);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+    use solana_program::pubkey::Pubkey;
+
+    use super::{Delegation, DelegateRestriction, MAX_DELEGATES};
+
+    #[test]
+    fn test_set_and_find() {
+        let mut delegation = Delegation::zeroed();
+        let key = Pubkey::new_unique();
+        assert!(delegation.set(key, 100, DelegateRestriction::NoTool as u64, 0));
+        assert!(delegation.find(&key, 99).unwrap().is_restricted(DelegateRestriction::NoTool));
+        assert!(delegation.find(&key, 100).is_none());
+
+        // Updating a delegate keeps its slot.
+        assert!(delegation.set(key, 200, 0, 0));
+        assert_eq!(delegation.delegates.iter().filter(|d| d.key.eq(&key)).count(), 1);
+        assert!(!delegation.find(&key, 150).unwrap().is_restricted(DelegateRestriction::NoTool));
+
+        delegation.remove(&key);
+        assert!(delegation.find(&key, 0).is_none());
+        assert!(delegation.find(&Pubkey::default(), 0).is_none());
+    }
+
+    #[test]
+    fn test_set_full() {
+        let mut delegation = Delegation::zeroed();
+        for i in 0..MAX_DELEGATES {
+            assert!(delegation.set(Pubkey::new_unique(), 100 + i as i64, 0, 0));
+        }
+        assert!(!delegation.set(Pubkey::new_unique(), 100, 0, 0));
+
+        // Expired delegates are replaced.
+        let key = Pubkey::new_unique();
+        assert!(delegation.set(key, 200, 0, 100));
+        assert!(delegation.find(&key, 100).is_some());
+    }
+}
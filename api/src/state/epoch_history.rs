@@ -0,0 +1,112 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    consts::EPOCH_HISTORY_LENGTH,
+    utils::{impl_account_from_bytes, impl_to_bytes, Discriminator},
+};
+
+use super::AccountDiscriminator;
+
+/// Epoch history accounts keep a summary of the most recent epochs of a resource. There is one
+/// epoch history per resource, and it is updated by every reset.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct EpochHistory {
+    /// The resource mint this history is for.
+    pub resource: Pubkey,
+
+    /// The total number of epochs recorded.
+    pub count: u64,
+
+    /// Ring buffer of epoch summaries. The most recent epoch is at `(count - 1) % EPOCH_HISTORY_LENGTH`.
+    pub epochs: [EpochSummary; EPOCH_HISTORY_LENGTH],
+}
+
+/// A summary of a single epoch, recorded at the reset which ends it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct EpochSummary {
+    /// The timestamp the epoch started at.
+    pub start_at: i64,
+
+    /// The timestamp the epoch was reset at.
+    pub end_at: i64,
+
+    /// The rewards the busses would have paid out if there were no limit.
+    pub theoretical_rewards: u64,
+
+    /// The rewards actually paid out.
+    pub rewards: u64,
+
+    /// The rewards left in the busses.
+    pub remaining_rewards: u64,
+
+    /// The base reward rate paid out during the epoch.
+    pub base_reward_rate: u64,
+
+    /// The minimum accepted difficulty during the epoch.
+    pub min_difficulty: u64,
+
+    /// The largest known stake balance seen during the epoch.
+    pub top_balance: u64,
+}
+
+impl EpochHistory {
+    /// Records an epoch, overwriting the oldest entry once the buffer is full.
+    pub fn push(&mut self, summary: EpochSummary) {
+        let index = (self.count % EPOCH_HISTORY_LENGTH as u64) as usize;
+        self.epochs[index] = summary;
+        self.count = self.count.saturating_add(1);
+    }
+
+    /// Returns the recorded epochs, most recent first.
+    pub fn iter(&self) -> impl Iterator<Item = &EpochSummary> {
+        let len = self.count.min(EPOCH_HISTORY_LENGTH as u64) as usize;
+        let head = self.count as usize;
+        (1..=len).map(move |i| &self.epochs[(head - i) % EPOCH_HISTORY_LENGTH])
+    }
+}
+
+impl Discriminator for EpochHistory {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::EpochHistory.into()
+    }
+}
+
+impl_to_bytes!(EpochHistory);
+impl_account_from_bytes!(EpochHistory);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::{EpochHistory, EpochSummary};
+    use crate::consts::EPOCH_HISTORY_LENGTH;
+
+    fn summary(start_at: i64) -> EpochSummary {
+        EpochSummary {
+            start_at,
+            ..EpochSummary::zeroed()
+        }
+    }
+
+    #[test]
+    fn test_epoch_history_empty() {
+        let history = EpochHistory::zeroed();
+        assert_eq!(history.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_epoch_history_wraps() {
+        let mut history = EpochHistory::zeroed();
+        let total = EPOCH_HISTORY_LENGTH as i64 + 3;
+        for i in 0..total {
+            history.push(summary(i));
+        }
+        let starts: Vec<i64> = history.iter().map(|e| e.start_at).collect();
+        assert_eq!(starts.len(), EPOCH_HISTORY_LENGTH);
+        assert_eq!(starts[0], total - 1);
+        assert_eq!(starts[EPOCH_HISTORY_LENGTH - 1], 3);
+    }
+}
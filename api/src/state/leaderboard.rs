@@ -0,0 +1,144 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    consts::{LEADERBOARD, LEADERBOARD_LENGTH},
+    utils::{impl_account_from_bytes, impl_to_bytes, Discriminator},
+};
+
+use super::AccountDiscriminator;
+
+/// Leaderboard accounts rank the proofs with the highest rewards in an epoch. Every bus has a
+/// leaderboard which is updated by mine, so leaderboards share the write-lock of their bus. After reset,
+/// the bus leaderboards are merged into the leaderboard of the resource and cleared for the next epoch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Leaderboard {
+    /// The resource mint this leaderboard is for.
+    pub resource: Pubkey,
+
+    /// The bus this leaderboard is for, or the default address for the resource leaderboard.
+    pub bus: Pubkey,
+
+    /// The reset timestamp of the epoch the entries belong to.
+    pub epoch_start_at: i64,
+
+    /// The ranked entries, in no particular order. Empty entries have zero rewards.
+    pub entries: [LeaderboardEntry; LEADERBOARD_LENGTH],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct LeaderboardEntry {
+    /// The ranked proof.
+    pub proof: Pubkey,
+
+    /// The rewards the proof earned in the epoch.
+    pub rewards: u64,
+}
+
+impl Leaderboard {
+    /// Records the epoch rewards of a proof. The proof's entry is updated if it is already ranked,
+    /// otherwise it replaces the smallest entry if its rewards are larger.
+    pub fn record(&mut self, proof: Pubkey, rewards: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.rewards.gt(&0) && e.proof.eq(&proof)) {
+            entry.rewards = entry.rewards.max(rewards);
+            return;
+        }
+
+        let smallest = self.entries.iter_mut().min_by_key(|e| e.rewards).unwrap();
+        if rewards.gt(&smallest.rewards) {
+            *smallest = LeaderboardEntry { proof, rewards };
+        }
+    }
+
+    /// Records every entry of another leaderboard.
+    pub fn merge(&mut self, other: &Leaderboard) {
+        for entry in other.entries.iter().filter(|e| e.rewards.gt(&0)) {
+            self.record(entry.proof, entry.rewards);
+        }
+    }
+
+    /// Returns whether the leaderboard has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(|e| e.rewards.eq(&0))
+    }
+
+    /// Clears all entries and starts a new epoch.
+    pub fn rotate(&mut self, epoch_start_at: i64) {
+        self.epoch_start_at = epoch_start_at;
+        self.entries = [LeaderboardEntry::zeroed(); LEADERBOARD_LENGTH];
+    }
+
+    /// Returns the non-empty entries, sorted by rewards in descending order.
+    pub fn ranked(&self) -> Vec<LeaderboardEntry> {
+        let mut entries: Vec<LeaderboardEntry> =
+            self.entries.iter().filter(|e| e.rewards.gt(&0)).copied().collect();
+        entries.sort_by(|a, b| b.rewards.cmp(&a.rewards));
+        entries
+    }
+}
+
+/// Derives the leaderboard PDA of a bus or resource mint.
+pub fn leaderboard_pda(key: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LEADERBOARD, key.as_ref()], &crate::id())
+}
+
+impl Discriminator for Leaderboard {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::Leaderboard.into()
+    }
+}
+
+impl_to_bytes!(Leaderboard);
+impl_account_from_bytes!(Leaderboard);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+    use solana_program::pubkey::Pubkey;
+
+    use super::Leaderboard;
+    use crate::consts::LEADERBOARD_LENGTH;
+
+    #[test]
+    fn test_leaderboard_record_updates_existing_entry() {
+        let mut leaderboard = Leaderboard::zeroed();
+        let proof = Pubkey::new_unique();
+        leaderboard.record(proof, 10);
+        leaderboard.record(proof, 30);
+        let ranked = leaderboard.ranked();
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].rewards, 30);
+    }
+
+    #[test]
+    fn test_leaderboard_record_replaces_smallest_entry() {
+        let mut leaderboard = Leaderboard::zeroed();
+        for i in 1..=LEADERBOARD_LENGTH as u64 {
+            leaderboard.record(Pubkey::new_unique(), i * 10);
+        }
+        let proof = Pubkey::new_unique();
+        leaderboard.record(proof, 5);
+        assert!(leaderboard.ranked().iter().all(|e| e.proof.ne(&proof)));
+        leaderboard.record(proof, 15);
+        let ranked = leaderboard.ranked();
+        assert_eq!(ranked.len(), LEADERBOARD_LENGTH);
+        assert_eq!(ranked[LEADERBOARD_LENGTH - 1].proof, proof);
+    }
+
+    #[test]
+    fn test_leaderboard_merge() {
+        let proof = Pubkey::new_unique();
+        let mut a = Leaderboard::zeroed();
+        let mut b = Leaderboard::zeroed();
+        a.record(proof, 10);
+        b.record(proof, 20);
+        b.record(Pubkey::new_unique(), 5);
+        a.merge(&b);
+        let ranked = a.ranked();
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].proof, proof);
+        assert_eq!(ranked[0].rewards, 20);
+    }
+}
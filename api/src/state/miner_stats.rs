@@ -0,0 +1,190 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
+
+use super::AccountDiscriminator;
+
+/// Miner stats accounts are an optional extension of a proof which track why a miner's
+/// earnings differ from expectations. They are updated by mine when provided, and are required
+/// to reprocess the proof for CHROMIUM.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MinerStats {
+    /// The proof these stats are for.
+    pub proof: Pubkey,
+
+    /// The highest difficulty ever submitted.
+    pub best_difficulty: u64,
+
+    /// The reset timestamp of the epoch the epoch hashes were submitted in.
+    pub epoch_start_at: i64,
+
+    /// The hashes submitted in the current epoch.
+    pub epoch_hashes: u64,
+
+    /// The rewards earned in the current epoch.
+    pub epoch_rewards: u64,
+
+    /// The number of consecutive hashes submitted without a liveness penalty.
+    pub streak: u64,
+
+    /// The longest streak ever.
+    pub best_streak: u64,
+
+    /// The total rewards lost to liveness penalties.
+    pub total_late_penalties: u64,
+
+    /// The total bonus rewards earned from tools.
+    pub total_tool_rewards: u64,
+
+    /// The total bonus rewards earned from guild stake.
+    pub total_stake_rewards: u64,
+
+    /// The lifetime hashes of the proof which have already been reprocessed.
+    pub reprocessed_hashes: u64,
+
+    /// The lifetime rewards of the proof which have already been reprocessed.
+    pub reprocessed_rewards: u64,
+}
+
+impl MinerStats {
+    /// Records a submitted hash.
+    pub fn record_hash(
+        &mut self,
+        epoch_start_at: i64,
+        difficulty: u64,
+        reward: u64,
+        late_penalty: u64,
+        tool_reward: u64,
+        stake_reward: u64,
+    ) {
+        if self.epoch_start_at.ne(&epoch_start_at) {
+            self.epoch_start_at = epoch_start_at;
+            self.epoch_hashes = 0;
+            self.epoch_rewards = 0;
+        }
+        self.epoch_hashes = self.epoch_hashes.saturating_add(1);
+        self.epoch_rewards = self.epoch_rewards.saturating_add(reward);
+        self.best_difficulty = self.best_difficulty.max(difficulty);
+
+        if late_penalty.eq(&0) {
+            self.streak = self.streak.saturating_add(1);
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+
+        self.total_late_penalties = self.total_late_penalties.saturating_add(late_penalty);
+        self.total_tool_rewards = self.total_tool_rewards.saturating_add(tool_reward);
+        self.total_stake_rewards = self.total_stake_rewards.saturating_add(stake_reward);
+    }
+
+    /// Returns the tool and stake bonuses actually paid out of a capped reward. The base reward is paid
+    /// first, so the bonuses are the first to be cut when the reward is capped by the buses.
+    pub fn paid_bonuses(base_reward: u64, reward_actual: u64, tool_reward: u64, stake_reward: u64) -> (u64, u64) {
+        let paid_bonus = reward_actual.saturating_sub(base_reward);
+        let paid_tool_reward = tool_reward.min(paid_bonus);
+        let paid_stake_reward = stake_reward.min(paid_bonus.saturating_sub(paid_tool_reward));
+        (paid_tool_reward, paid_stake_reward)
+    }
+
+    /// Returns the hashes and rewards of the proof since the last reprocess, given its lifetime hashes and
+    /// rewards.
+    pub fn unreprocessed(&self, total_hashes: u64, total_rewards: u64) -> (u64, u64) {
+        (
+            total_hashes.saturating_sub(self.reprocessed_hashes),
+            total_rewards.saturating_sub(self.reprocessed_rewards),
+        )
+    }
+
+    /// Records a reprocess of the proof, given its lifetime hashes and rewards. Only the share
+    /// `minted / reward` of the hashes and rewards since the last reprocess is consumed, so the share whose
+    /// reward could not be minted can be reprocessed later.
+    pub fn record_reprocess(&mut self, total_hashes: u64, total_rewards: u64, minted: u64, reward: u64) {
+        let (hashes, rewards) = self.unreprocessed(total_hashes, total_rewards);
+        let consumed = |amount: u64| {
+            if minted.ge(&reward) {
+                return amount;
+            }
+            ((amount as u128) * (minted as u128) / (reward as u128)) as u64
+        };
+        self.reprocessed_hashes = self.reprocessed_hashes.saturating_add(consumed(hashes));
+        self.reprocessed_rewards = self.reprocessed_rewards.saturating_add(consumed(rewards));
+    }
+}
+
+impl Discriminator for MinerStats {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::MinerStats.into()
+    }
+}
+
+impl_to_bytes!(MinerStats);
+impl_account_from_bytes!(MinerStats);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::MinerStats;
+
+    #[test]
+    fn test_record_hash_streak() {
+        let mut stats = MinerStats::zeroed();
+        stats.record_hash(100, 10, 50, 0, 0, 0);
+        stats.record_hash(100, 12, 50, 0, 0, 0);
+        assert_eq!(stats.streak, 2);
+        assert_eq!(stats.best_streak, 2);
+
+        // A late hash breaks the streak and records the penalty.
+        stats.record_hash(100, 11, 25, 25, 0, 0);
+        assert_eq!(stats.streak, 0);
+        assert_eq!(stats.best_streak, 2);
+        assert_eq!(stats.total_late_penalties, 25);
+        assert_eq!(stats.best_difficulty, 12);
+    }
+
+    #[test]
+    fn test_record_hash_epoch() {
+        let mut stats = MinerStats::zeroed();
+        stats.record_hash(100, 10, 50, 0, 5, 7);
+        stats.record_hash(100, 10, 50, 0, 5, 7);
+        assert_eq!(stats.epoch_hashes, 2);
+        assert_eq!(stats.epoch_rewards, 100);
+
+        // A new epoch restarts the epoch totals, but not the lifetime totals.
+        stats.record_hash(200, 10, 50, 0, 5, 7);
+        assert_eq!(stats.epoch_start_at, 200);
+        assert_eq!(stats.epoch_hashes, 1);
+        assert_eq!(stats.epoch_rewards, 50);
+        assert_eq!(stats.total_tool_rewards, 15);
+        assert_eq!(stats.total_stake_rewards, 21);
+    }
+
+    #[test]
+    fn test_record_reprocess() {
+        let mut stats = MinerStats::zeroed();
+        stats.record_reprocess(10, 1000, 50, 50);
+        assert_eq!(stats.unreprocessed(10, 1000), (0, 0));
+        assert_eq!(stats.unreprocessed(14, 1400), (4, 400));
+
+        // Only the share of the reward which was minted is consumed.
+        stats.record_reprocess(14, 1400, 15, 60);
+        assert_eq!(stats.unreprocessed(14, 1400), (3, 300));
+        stats.record_reprocess(14, 1400, 0, 60);
+        assert_eq!(stats.unreprocessed(14, 1400), (3, 300));
+
+        // A reprocess without reward consumes everything.
+        stats.record_reprocess(14, 1400, 0, 0);
+        assert_eq!(stats.unreprocessed(14, 1400), (0, 0));
+    }
+
+    #[test]
+    fn test_paid_bonuses() {
+        assert_eq!(MinerStats::paid_bonuses(100, 150, 30, 20), (30, 20));
+        assert_eq!(MinerStats::paid_bonuses(100, 140, 30, 20), (30, 10));
+        assert_eq!(MinerStats::paid_bonuses(100, 120, 30, 20), (20, 0));
+        assert_eq!(MinerStats::paid_bonuses(100, 80, 30, 20), (0, 0));
+    }
+}
@@ -1,17 +1,33 @@
 mod bus;
+mod chromium_config;
+mod chromium_stake;
 mod config;
+mod delegation;
+mod epoch_history;
+mod leaderboard;
+mod miner_stats;
 mod proof;
+mod proof_transfer;
 mod proof_v2;
 mod treasury;
 mod tool;
 mod reprocessor;
+mod resource_config;
 pub use bus::*;
+pub use chromium_config::*;
+pub use chromium_stake::*;
 pub use config::*;
+pub use delegation::*;
+pub use epoch_history::*;
+pub use leaderboard::*;
+pub use miner_stats::*;
 pub use proof::*;
+pub use proof_transfer::*;
 pub use proof_v2::*;
 pub use treasury::*;
 pub use tool::*;
 pub use reprocessor::*;
+pub use resource_config::*;
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
@@ -27,4 +43,12 @@ pub enum AccountDiscriminator {
     Tool = 107,
     Reprocessor = 108,
     WoodTool = 109,
+    EpochHistory = 110,
+    MinerStats = 111,
+    Leaderboard = 112,
+    ResourceConfig = 113,
+    ChromiumConfig = 114,
+    ChromiumStake = 115,
+    Delegation = 116,
+    ProofTransfer = 117,
 }
@@ -0,0 +1,27 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
+
+use super::AccountDiscriminator;
+
+/// ProofTransfer is an account (1 per proof) which holds a proposed transfer of the proof to a new
+/// authority. The transfer is executed once the new authority accepts it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ProofTransfer {
+    /// The proof to transfer.
+    pub proof: Pubkey,
+
+    /// The authority the proof is transferred to.
+    pub new_authority: Pubkey,
+}
+
+impl Discriminator for ProofTransfer {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::ProofTransfer.into()
+    }
+}
+
+impl_to_bytes!(ProofTransfer);
+impl_account_from_bytes!(ProofTransfer);
@@ -1,7 +1,10 @@
 use bytemuck::{Pod, Zeroable};
-use solana_program::pubkey::Pubkey;
+use solana_program::{pubkey::Pubkey, slot_hashes::MAX_ENTRIES};
 
-use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
+use crate::{
+    consts::*,
+    utils::{impl_account_from_bytes, impl_to_bytes, Discriminator},
+};
 
 use super::AccountDiscriminator;
 
@@ -12,10 +15,49 @@ use super::AccountDiscriminator;
 pub struct Reprocessor {
     /// The reprocess authority.
     pub authority: Pubkey,
-    /// The slot the reprocessor was created at.
+    /// The target slot. The reprocess can be finalized with the hash of the first slot after it.
     pub slot: u64,
-    /// Sysvar hashes
+    /// Unused. Kept for the layout of the account.
     pub hash: [u8; 32],
+    /// The hash of the secret revealed when the reprocess is finalized.
+    pub commitment: [u8; 32],
+    /// The keeper allowed to finalize on behalf of the authority, or the default address if none.
+    pub keeper: Pubkey,
+    /// The lamports held by the reprocessor and paid to the keeper if it finalizes.
+    pub keeper_tip: u64,
+    /// The proof whose stats are reprocessed.
+    pub proof: Pubkey,
+    /// The miner stats tracking how much of the proof's stats has been reprocessed.
+    pub miner_stats: Pubkey,
+}
+
+impl Reprocessor {
+    /// The size of a reprocessor account, including its discriminator, created before reprocessors were bound
+    /// to a proof. It only holds the authority, slot and hash.
+    pub const LEGACY_SIZE: usize = 8 + 72;
+
+    /// Returns whether the given address is allowed to finalize the reprocess.
+    pub fn can_finalize(&self, signer: &Pubkey) -> bool {
+        self.authority.eq(signer) || (self.keeper.ne(&Pubkey::default()) && self.keeper.eq(signer))
+    }
+
+    /// Returns the number of times the reward multiplier is halved when finalizing at the given slot.
+    pub fn halvings(&self, current_slot: u64) -> u64 {
+        current_slot.saturating_sub(self.slot.saturating_add(REPROCESS_SLOT_BUFFER))
+    }
+
+    /// Returns the highest reward multiplier finalizing at the given slot can produce.
+    pub fn max_multiplier(&self, current_slot: u64) -> u64 {
+        let halvings = u32::try_from(self.halvings(current_slot)).unwrap_or(u32::MAX);
+        REPROCESS_MAX_MULTIPLIER.checked_shr(halvings).unwrap_or(0).max(1)
+    }
+
+    /// Returns whether finalizing at the given slot is still worthwhile. Finalizing is not possible before
+    /// the target slot, or once the hash of the target slot has left the slot hashes sysvar. In between,
+    /// finalizing always mints at least the reward of a multiplier of 1, so it is never worse than cancelling.
+    pub fn is_worthwhile(&self, current_slot: u64) -> bool {
+        current_slot.gt(&self.slot) && current_slot.le(&self.slot.saturating_add(MAX_ENTRIES as u64))
+    }
 }
 
 impl Discriminator for Reprocessor {
@@ -26,3 +68,40 @@ impl Discriminator for Reprocessor {
 
 impl_to_bytes!(Reprocessor);
 impl_account_from_bytes!(Reprocessor);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+    use solana_program::{pubkey::Pubkey, slot_hashes::MAX_ENTRIES};
+
+    use crate::consts::*;
+
+    use super::Reprocessor;
+
+    #[test]
+    fn test_is_worthwhile() {
+        let mut reprocessor = Reprocessor::zeroed();
+        reprocessor.slot = 100;
+        assert!(!reprocessor.is_worthwhile(100));
+        assert!(reprocessor.is_worthwhile(101));
+        assert_eq!(reprocessor.max_multiplier(100 + REPROCESS_SLOT_BUFFER), REPROCESS_MAX_MULTIPLIER);
+        assert_eq!(reprocessor.max_multiplier(100 + REPROCESS_SLOT_BUFFER + 1), REPROCESS_MAX_MULTIPLIER / 2);
+        assert_eq!(reprocessor.max_multiplier(u64::MAX), 1);
+
+        // Finalizing is still worthwhile once every multiplier is 1, as long as the slot hash is available.
+        assert!(reprocessor.is_worthwhile(100 + REPROCESS_SLOT_BUFFER + 6));
+        assert!(reprocessor.is_worthwhile(100 + MAX_ENTRIES as u64));
+        assert!(!reprocessor.is_worthwhile(101 + MAX_ENTRIES as u64));
+    }
+
+    #[test]
+    fn test_can_finalize() {
+        let mut reprocessor = Reprocessor::zeroed();
+        reprocessor.authority = Pubkey::new_unique();
+        assert!(reprocessor.can_finalize(&reprocessor.authority));
+        assert!(!reprocessor.can_finalize(&Pubkey::default()));
+        reprocessor.keeper = Pubkey::new_unique();
+        assert!(reprocessor.can_finalize(&reprocessor.keeper));
+        assert!(!reprocessor.can_finalize(&Pubkey::new_unique()));
+    }
+}
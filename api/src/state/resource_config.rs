@@ -0,0 +1,136 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    consts::WOOD_PROPOGATION_RATE,
+    emission::*,
+    utils::{impl_account_from_bytes, impl_to_bytes, Discriminator},
+};
+
+use super::AccountDiscriminator;
+
+/// The denominator of values expressed in basis points.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// ResourceConfig is an account (1 per resource) which holds the emission parameters set by the admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ResourceConfig {
+    /// The resource mint this config is for.
+    pub resource: Pubkey,
+
+    /// The maximum token supply, or zero if the supply is uncapped.
+    pub max_supply: u64,
+
+    /// The maximum quantity of rewards a bus can hold per epoch.
+    pub max_bus_epoch_rewards: u64,
+
+    /// The share of the max bus rewards which decays every epoch, in basis points.
+    pub epoch_rewards_decay: u64,
+
+    /// The emission model of the resource, as an `EmissionModelKind`.
+    pub emission_model: u64,
+
+    /// The minimum quantity of rewards a bus can hold per epoch.
+    pub min_bus_epoch_rewards: u64,
+
+    /// The total rewards the buses were topped up with for the current epoch.
+    pub epoch_rewards: u64,
+
+    /// The duration of an epoch, in seconds.
+    pub epoch_duration: i64,
+
+    /// The number of buses in use. Buses can be added, but never removed.
+    pub bus_count: u64,
+
+    /// The quantity minted to the signer of a reset, as an incentive to crank the epoch.
+    pub reset_bounty: u64,
+
+    /// The leading 128 bits of the 256-bit threshold a hash must be below, as a little-endian integer.
+    pub difficulty_target: [u8; 16],
+
+    /// The number of halvings of the halving emission model.
+    pub halving_count: u64,
+}
+
+impl ResourceConfig {
+    /// Returns the quantity which can still be minted on top of the given supply.
+    pub fn remaining_supply(&self, supply: u64) -> u64 {
+        if self.max_supply.eq(&0) {
+            return u64::MAX;
+        }
+        self.max_supply.saturating_sub(supply)
+    }
+
+    /// Returns the difficulty target hashes must meet.
+    pub fn difficulty_target(&self) -> u128 {
+        u128::from_le_bytes(self.difficulty_target)
+    }
+
+    /// Sets the difficulty target hashes must meet.
+    pub fn set_difficulty_target(&mut self, target: u128) {
+        self.difficulty_target = target.to_le_bytes();
+    }
+
+    /// Returns the emission model selected by the config.
+    pub fn emission_model(&self) -> Result<Box<dyn EmissionModel>, ProgramError> {
+        let kind = u8::try_from(self.emission_model)
+            .ok()
+            .and_then(|kind| EmissionModelKind::try_from(kind).ok())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok(match kind {
+            EmissionModelKind::Fixed => Box::new(FixedEmission {
+                bus_epoch_rewards: self.max_bus_epoch_rewards,
+            }),
+            EmissionModelKind::Propagation => Box::new(PropagationEmission {
+                propagation_rate: WOOD_PROPOGATION_RATE,
+                min_bus_epoch_rewards: self.min_bus_epoch_rewards,
+                max_bus_epoch_rewards: self.max_bus_epoch_rewards,
+            }),
+            EmissionModelKind::Halving => Box::new(HalvingEmission {
+                initial_bus_epoch_rewards: self.max_bus_epoch_rewards,
+                max_supply: self.max_supply,
+                halving_count: self.halving_count,
+            }),
+            EmissionModelKind::Decaying => Box::new(DecayingEmission {
+                max_bus_epoch_rewards: self.max_bus_epoch_rewards,
+                min_bus_epoch_rewards: self.min_bus_epoch_rewards,
+                epoch_rewards_decay: self.epoch_rewards_decay,
+            }),
+        })
+    }
+}
+
+impl Discriminator for ResourceConfig {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::ResourceConfig.into()
+    }
+}
+
+impl_to_bytes!(ResourceConfig);
+impl_account_from_bytes!(ResourceConfig);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::ResourceConfig;
+
+    #[test]
+    fn test_remaining_supply() {
+        let mut config = ResourceConfig::zeroed();
+        assert_eq!(config.remaining_supply(100), u64::MAX);
+        config.max_supply = 150;
+        assert_eq!(config.remaining_supply(100), 50);
+        assert_eq!(config.remaining_supply(200), 0);
+    }
+
+    #[test]
+    fn test_emission_model() {
+        let mut config = ResourceConfig::zeroed();
+        config.max_bus_epoch_rewards = 100;
+        assert!(config.emission_model().is_ok());
+        config.emission_model = 4;
+        assert!(config.emission_model().is_err());
+    }
+}
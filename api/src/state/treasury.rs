@@ -5,10 +5,26 @@ use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
 use super::AccountDiscriminator;
 
 /// Treasury is a singleton account which is the mint authority for the ORE token and the authority of
-/// the program's global token account.
+/// the program's global token account. It also collects the SOL fees paid to reprocess.
+///
+/// The treasury was originally created without any fields, so it is reallocated before its fields are
+/// first written.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
-pub struct Treasury {}
+pub struct Treasury {
+    /// The lamports collected in fees.
+    pub fees_collected: u64,
+
+    /// The lamports withdrawn from the collected fees.
+    pub fees_withdrawn: u64,
+}
+
+impl Treasury {
+    /// Returns the collected fees which have not been withdrawn yet.
+    pub fn unwithdrawn_fees(&self) -> u64 {
+        self.fees_collected.saturating_sub(self.fees_withdrawn)
+    }
+}
 
 impl Discriminator for Treasury {
     fn discriminator() -> u8 {
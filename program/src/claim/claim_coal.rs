@@ -1,4 +1,4 @@
-use coal_api::{consts::*, error::CoalError, instruction::ClaimArgs, loaders::*, state::Proof};
+use coal_api::{consts::*, error::CoalError, event::ClaimEvent, instruction::ClaimArgs, loaders::*, state::Proof};
 use coal_utils::spl::transfer_signed;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
@@ -31,6 +31,8 @@ pub fn process_claim_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         .balance
         .checked_sub(amount)
         .ok_or(CoalError::ClaimTooLarge)?;
+    let balance = proof.balance;
+    drop(proof_data);
 
     // Transfer tokens from treasury to beneficiary.
     transfer_signed(
@@ -42,5 +44,15 @@ pub fn process_claim_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         &[&[TREASURY, &[TREASURY_BUMP]]],
     )?;
 
+    // Log the claim.
+    ClaimEvent {
+        authority: *signer.key,
+        resource: COAL_MINT_ADDRESS,
+        beneficiary: *beneficiary_info.key,
+        amount,
+        balance,
+    }
+    .log();
+
     Ok(())
 }
\ No newline at end of file
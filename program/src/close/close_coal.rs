@@ -1,4 +1,4 @@
-use coal_api::{loaders::*, state::Proof};
+use coal_api::{consts::{COAL_MINT_ADDRESS, DELEGATION, MINER_STATS, PROOF_TRANSFER}, event::CloseEvent, loaders::*, state::Proof};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     system_program,
@@ -6,10 +6,14 @@ use solana_program::{
 
 use crate::utils::AccountDeserialize;
 
+use super::{close_chromium_stake, close_proof_account};
+
 /// Close closes a proof account and returns the rent to the owner.
 pub fn process_close_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer, proof_info, system_program] = accounts else {
+    let [signer, proof_info, system_program, miner_stats_info, proof_transfer_info, delegation_info, chromium_stake_info] =
+        accounts
+    else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
@@ -24,6 +28,12 @@ pub fn process_close_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
     }
     drop(proof_data);
 
+    // Close the CHROMIUM stake, miner stats, pending proof transfer and delegation, if any.
+    close_chromium_stake(chromium_stake_info, proof_info.key, signer)?;
+    close_proof_account(miner_stats_info, MINER_STATS, proof_info.key, signer)?;
+    close_proof_account(proof_transfer_info, PROOF_TRANSFER, proof_info.key, signer)?;
+    close_proof_account(delegation_info, DELEGATION, proof_info.key, signer)?;
+
     // Realloc data to zero.
     proof_info.realloc(0, true)?;
 
@@ -31,5 +41,12 @@ pub fn process_close_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
     **signer.lamports.borrow_mut() += proof_info.lamports();
     **proof_info.lamports.borrow_mut() = 0;
 
+    // Log the close.
+    CloseEvent {
+        authority: *signer.key,
+        resource: COAL_MINT_ADDRESS,
+    }
+    .log();
+
     Ok(())
 }
\ No newline at end of file
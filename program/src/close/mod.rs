@@ -1,7 +1,12 @@
-use coal_api::state::{Proof, ProofV2};
-use coal_utils::Discriminator;
+use coal_api::{
+    consts::CHROMIUM_STAKE,
+    error::CoalError,
+    loaders::load_chromium_stake,
+    state::{ChromiumStake, Proof, ProofV2},
+};
+use coal_utils::{AccountDeserialize, Discriminator};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult,
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey,
 };
 
 use close_coal::*;
@@ -23,3 +28,49 @@ pub fn process_close<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8])
 
     return Err(solana_program::program_error::ProgramError::InvalidAccountData);
 }
+
+/// Closes an account addressed by a proof, such as its miner stats, if it exists. The rent is returned to the
+/// recipient. A proof reopened at the same address must not inherit the accounts of the closed proof.
+pub(crate) fn close_proof_account(
+    info: &AccountInfo<'_>,
+    seed: &[u8],
+    proof: &Pubkey,
+    recipient: &AccountInfo<'_>,
+) -> ProgramResult {
+    let address = Pubkey::find_program_address(&[seed, proof.as_ref()], &coal_api::id()).0;
+    if info.key.ne(&address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    if info.owner.ne(&coal_api::id()) || !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Realloc data to zero.
+    info.realloc(0, true)?;
+
+    // Send remaining lamports to recipient.
+    **recipient.lamports.borrow_mut() += info.lamports();
+    **info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
+
+/// Closes the CHROMIUM stake of a proof, if it exists. The staked CHROMIUM is held by the treasury against the
+/// proof, so the stake must be withdrawn first.
+pub(crate) fn close_chromium_stake(
+    info: &AccountInfo<'_>,
+    proof: &Pubkey,
+    recipient: &AccountInfo<'_>,
+) -> ProgramResult {
+    if !info.data_is_empty() {
+        load_chromium_stake(info, proof, true)?;
+        let chromium_stake = ChromiumStake::try_from_bytes(&info.data.borrow())?.to_owned();
+        if chromium_stake.balance.gt(&0) || chromium_stake.unstaking_balance.gt(&0) {
+            return Err(CoalError::StakeNotWithdrawn.into());
+        }
+    }
+    close_proof_account(info, CHROMIUM_STAKE, proof, recipient)
+}
@@ -1,6 +1,6 @@
 use std::mem::size_of;
 
-use coal_api::{consts::*, error::CoalError, instruction::EquipArgs, loaders::*, state::{Tool, WoodTool}};
+use coal_api::{consts::*, error::CoalError, event::EquipEvent, instruction::EquipArgs, loaders::*, state::{Tool, WoodTool}};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, system_program
 };
@@ -38,7 +38,7 @@ pub fn process_equip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
 	msg!("durability: {}", durability);
 	msg!("multiplier: {}", multiplier);
 	
-    match resource.as_str() {
+    let resource = match resource.as_str() {
         "wood" => {
             handle_equip_wood_tool(
                 tool_info,
@@ -51,6 +51,7 @@ pub fn process_equip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
                 multiplier,
                 args.bump
             )?;
+            WOOD_MINT_ADDRESS
         }
         "coal" => {
             handle_equip_coal_tool(
@@ -64,11 +65,22 @@ pub fn process_equip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
                 multiplier,
                 args.bump
             )?;
+            COAL_MINT_ADDRESS
         }
         _ => {
             return Err(CoalError::InvalidResource.into());
         }
+    };
+
+    // Log the equip.
+    EquipEvent {
+        authority: *signer.key,
+        resource,
+        asset: *asset_info.key,
+        durability: amount_f64_to_u64(durability),
+        multiplier,
     }
+    .log();
 
 	Ok(())
 }
@@ -0,0 +1,64 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    instruction::InitBusArgs,
+    loaders::*,
+    state::Bus,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+
+/// Creates a bus account of a resource. The bus holds no rewards until it is in use and topped up by reset.
+pub fn process_init_bus(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = InitBusArgs::try_from_bytes(data)?;
+    if (args.id as usize).ge(&MAX_BUS_COUNT) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Load accounts.
+    let [signer, mint_info, bus_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    let seed = match *mint_info.key {
+        COAL_MINT_ADDRESS => COAL_BUS,
+        WOOD_MINT_ADDRESS => WOOD_BUS,
+        _ => return Err(CoalError::InvalidResource.into()),
+    };
+    load_mint(mint_info, *mint_info.key, false)?;
+    load_uninitialized_pda(bus_info, &[seed, &[args.id]], args.bump, &coal_api::id())?;
+    load_program(system_program, system_program::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Initialize bus.
+    create_pda(
+        bus_info,
+        &coal_api::id(),
+        8 + size_of::<Bus>(),
+        &[seed, &[args.id], &[args.bump]],
+        system_program,
+        signer,
+    )?;
+    let mut bus_data = bus_info.data.borrow_mut();
+    bus_data[0] = Bus::discriminator();
+    let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+    bus.id = args.id as u64;
+
+    Ok(())
+}
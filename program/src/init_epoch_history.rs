@@ -0,0 +1,65 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    instruction::InitEpochHistoryArgs,
+    loaders::*,
+    state::EpochHistory,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+
+/// Creates the epoch history account of a resource.
+pub fn process_init_epoch_history(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = InitEpochHistoryArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, mint_info, epoch_history_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    if mint_info.key.ne(&COAL_MINT_ADDRESS) && mint_info.key.ne(&WOOD_MINT_ADDRESS) {
+        return Err(CoalError::InvalidResource.into());
+    }
+    load_mint(mint_info, *mint_info.key, false)?;
+    load_uninitialized_pda(
+        epoch_history_info,
+        &[EPOCH_HISTORY, mint_info.key.as_ref()],
+        args.bump,
+        &coal_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Initialize epoch history.
+    create_pda(
+        epoch_history_info,
+        &coal_api::id(),
+        8 + size_of::<EpochHistory>(),
+        &[EPOCH_HISTORY, mint_info.key.as_ref(), &[args.bump]],
+        system_program,
+        signer,
+    )?;
+    let mut epoch_history_data = epoch_history_info.data.borrow_mut();
+    epoch_history_data[0] = EpochHistory::discriminator();
+    let epoch_history = EpochHistory::try_from_bytes_mut(&mut epoch_history_data)?;
+    epoch_history.resource = *mint_info.key;
+    epoch_history.count = 0;
+
+    Ok(())
+}
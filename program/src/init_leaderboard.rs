@@ -0,0 +1,79 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    event::InitLeaderboardEvent,
+    instruction::InitLeaderboardArgs,
+    loaders::*,
+    state::Leaderboard,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+
+/// Creates the leaderboard account of a bus, or of the resource itself if no bus is provided.
+pub fn process_init_leaderboard(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = InitLeaderboardArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, mint_info, leaderboard_info, system_program, bus_infos @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_mint(mint_info, *mint_info.key, false)?;
+    let bus_info = bus_infos.first();
+    match (*mint_info.key, bus_info) {
+        (COAL_MINT_ADDRESS, Some(bus_info)) => load_any_coal_bus(bus_info, false)?,
+        (WOOD_MINT_ADDRESS, Some(bus_info)) => load_any_wood_bus(bus_info, false)?,
+        (COAL_MINT_ADDRESS, None) | (WOOD_MINT_ADDRESS, None) => {}
+        _ => return Err(CoalError::InvalidResource.into()),
+    }
+    let key = bus_info.map_or(mint_info.key, |bus_info| bus_info.key);
+    load_uninitialized_pda(
+        leaderboard_info,
+        &[LEADERBOARD, key.as_ref()],
+        args.bump,
+        &coal_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Initialize leaderboard.
+    create_pda(
+        leaderboard_info,
+        &coal_api::id(),
+        8 + size_of::<Leaderboard>(),
+        &[LEADERBOARD, key.as_ref(), &[args.bump]],
+        system_program,
+        signer,
+    )?;
+    let mut leaderboard_data = leaderboard_info.data.borrow_mut();
+    leaderboard_data[0] = Leaderboard::discriminator();
+    let leaderboard = Leaderboard::try_from_bytes_mut(&mut leaderboard_data)?;
+    leaderboard.resource = *mint_info.key;
+    leaderboard.bus = bus_info.map_or(Pubkey::default(), |bus_info| *bus_info.key);
+
+    // Log the initialization.
+    InitLeaderboardEvent {
+        resource: leaderboard.resource,
+        bus: leaderboard.bus,
+    }
+    .log();
+
+    Ok(())
+}
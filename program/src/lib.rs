@@ -1,28 +1,51 @@
 mod claim;
 mod close;
+mod init_bus;
 mod init_chromium;
+mod init_epoch_history;
+mod init_leaderboard;
+mod merge_leaderboards;
 mod mine;
 mod open_coal;
+mod open_miner_stats;
 mod open_wood;
 mod reset;
+mod set_chromium_config;
+mod set_delegate;
+mod set_resource_config;
 mod stake;
+mod transfer;
 mod update;
+mod withdraw_fees;
 mod equip;
 mod unequip;
 mod reprocess;
+#[cfg(test)]
+mod test_utils;
 use claim::*;
 use close::*;
+use init_bus::*;
 use init_chromium::*;
+use init_epoch_history::*;
+use init_leaderboard::*;
+use merge_leaderboards::*;
 use mine::*;
 use open_coal::*;
+use open_miner_stats::*;
 use open_wood::*;
 use reset::*;
+use set_chromium_config::*;
+use set_delegate::*;
+use set_resource_config::*;
 use stake::*;
+use transfer::*;
 use update::*;
+use withdraw_fees::*;
 use equip::*;
 use unequip::*;
 use reprocess::reprocess::*;
 use reprocess::init_reprocess::*;
+use reprocess::cancel_reprocess::*;
 use coal_api::instruction::*;
 use solana_program::{
     self, account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
@@ -55,13 +78,31 @@ pub fn process_instruction(
         CoalInstruction::OpenCoal => process_open_coal(accounts, data)?,
         CoalInstruction::OpenWood => process_open_wood(accounts, data)?,
         CoalInstruction::Reset => process_reset(accounts, data)?,
+        CoalInstruction::ResetAll => process_reset_all(accounts, data)?,
+        CoalInstruction::MergeLeaderboards => process_merge_leaderboards(accounts, data)?,
         CoalInstruction::Stake => process_stake(accounts, data)?,
+        CoalInstruction::StakeChromium => process_stake_chromium(accounts, data)?,
+        CoalInstruction::UnstakeChromium => process_unstake_chromium(accounts, data)?,
+        CoalInstruction::WithdrawChromium => process_withdraw_chromium(accounts, data)?,
         CoalInstruction::Update => process_update(accounts, data)?,
+        CoalInstruction::SetDelegate => process_set_delegate(accounts, data)?,
+        CoalInstruction::TransferProof => process_transfer_proof(accounts, data)?,
+        CoalInstruction::ProposeProofTransfer => process_propose_proof_transfer(accounts, data)?,
+        CoalInstruction::AcceptProofTransfer => process_accept_proof_transfer(accounts, data)?,
+        CoalInstruction::CancelProofTransfer => process_cancel_proof_transfer(accounts, data)?,
         CoalInstruction::InitChromium => process_init_chromium(accounts, data)?,
+        CoalInstruction::InitEpochHistory => process_init_epoch_history(accounts, data)?,
+        CoalInstruction::InitLeaderboard => process_init_leaderboard(accounts, data)?,
+        CoalInstruction::SetResourceConfig => process_set_resource_config(accounts, data)?,
+        CoalInstruction::SetChromiumConfig => process_set_chromium_config(accounts, data)?,
+        CoalInstruction::WithdrawFees => process_withdraw_fees(accounts, data)?,
+        CoalInstruction::InitBus => process_init_bus(accounts, data)?,
         CoalInstruction::Equip => process_equip_tool(accounts, data)?,
         CoalInstruction::Unequip => process_unequip_tool(accounts, data)?,
         CoalInstruction::InitReprocess => process_initialize_reprocess(accounts, data)?,
         CoalInstruction::FinalizeReprocess => process_reprocess(accounts, data)?,
+        CoalInstruction::CancelReprocess => process_cancel_reprocess(accounts, data)?,
+        CoalInstruction::OpenMinerStats => process_open_miner_stats(accounts, data)?,
     }
 
     Ok(())
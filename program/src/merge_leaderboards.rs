@@ -0,0 +1,77 @@
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    event::MergeLeaderboardsEvent,
+    loaders::*,
+    state::{Config, Leaderboard, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Merges the bus leaderboards of the epoch which just ended into the leaderboard of the resource, and clears
+/// them for the current epoch. Merging is cranked separately from reset, so reset does not have to lock every
+/// bus leaderboard. The bus leaderboards can be merged in any number of calls.
+pub fn process_merge_leaderboards(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, config_info, leaderboard_info, bus_leaderboard_infos @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    let (resource, last_reset_at) = match *config_info.key {
+        COAL_CONFIG_ADDRESS => {
+            load_coal_config(config_info, false)?;
+            (COAL_MINT_ADDRESS, Config::try_from_bytes(&config_info.data.borrow())?.last_reset_at)
+        }
+        WOOD_CONFIG_ADDRESS => {
+            load_wood_config(config_info, false)?;
+            (WOOD_MINT_ADDRESS, WoodConfig::try_from_bytes(&config_info.data.borrow())?.last_reset_at)
+        }
+        _ => return Err(CoalError::InvalidResource.into()),
+    };
+    load_leaderboard(leaderboard_info, &resource, &Pubkey::default(), true)?;
+
+    // Merge the bus leaderboards.
+    //
+    // The resource leaderboard holds the final standings of the last epoch which ended. A bus leaderboard of an
+    // older epoch is cleared without being merged, and one of the current epoch is left unchanged.
+    let mut leaderboard_data = leaderboard_info.data.borrow_mut();
+    let leaderboard = Leaderboard::try_from_bytes_mut(&mut leaderboard_data)?;
+    let mut merged = 0u64;
+    for bus_leaderboard_info in bus_leaderboard_infos {
+        let bus = Leaderboard::try_from_bytes(&bus_leaderboard_info.data.borrow())?.bus;
+        if bus.eq(&Pubkey::default()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        load_leaderboard(bus_leaderboard_info, &resource, &bus, true)?;
+        let mut bus_leaderboard_data = bus_leaderboard_info.data.borrow_mut();
+        let bus_leaderboard = Leaderboard::try_from_bytes_mut(&mut bus_leaderboard_data)?;
+        let epoch_start_at = bus_leaderboard.epoch_start_at;
+        if epoch_start_at.ge(&last_reset_at) {
+            continue;
+        }
+        if leaderboard.epoch_start_at.lt(&epoch_start_at) {
+            leaderboard.rotate(epoch_start_at);
+        }
+        if leaderboard.epoch_start_at.eq(&epoch_start_at) {
+            leaderboard.merge(bus_leaderboard);
+            merged = merged.saturating_add(1);
+        }
+        bus_leaderboard.rotate(last_reset_at);
+    }
+
+    // Log the merge.
+    MergeLeaderboardsEvent {
+        resource,
+        epoch_start_at: leaderboard.epoch_start_at,
+        merged,
+    }
+    .log();
+
+    Ok(())
+}
@@ -3,11 +3,12 @@ use std::mem::size_of;
 use drillx::Solution;
 use coal_api::{
     consts::*,
+    difficulty::{calculate_hash_reward, meets_difficulty_target},
     error::CoalError,
     event::MineEvent,
-    instruction::MineArgs,
+    instruction::{MineArgsV2, MineOptionalAccount},
     loaders::*,
-    state::{Bus, ProofV2, WoodConfig, WoodTool},
+    state::{Bus, ChromiumStake, Leaderboard, MinerStats, ProofV2, ResourceConfig, WoodConfig, WoodTool},
 };
 use solana_program::msg;
 #[allow(deprecated)]
@@ -27,22 +28,41 @@ use solana_program::{
 
 use crate::utils::AccountDeserialize;
 
+use super::{authenticate_miner, next_optional_account};
+
 pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     // Parse args.
-    let args = MineArgs::try_from_bytes(data)?;
+    let args = MineArgsV2::try_from_bytes(data)?;
+    if args.has_unknown() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
 
     // Load accounts.
-    let (required_accounts, optional_accounts) = accounts.split_at(6);
-    let [signer, bus_info, config_info, proof_info, instructions_sysvar, slot_hashes_sysvar] = required_accounts
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(7));
+    let [signer, bus_info, config_info, proof_info, instructions_sysvar, slot_hashes_sysvar, resource_config_info] =
+        required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
     load_any_wood_bus(bus_info, true)?;
     load_wood_config(config_info, false)?;
-    load_proof_v2_with_miner(proof_info, signer.key, &WOOD_MINT_ADDRESS, true)?;
+    load_any_proof_v2(proof_info, true)?;
     load_sysvar(instructions_sysvar, sysvar::instructions::id())?;
     load_sysvar(slot_hashes_sysvar, sysvar::slot_hashes::id())?;
+    load_resource_config(resource_config_info, &WOOD_MINT_ADDRESS, false)?;
+
+    // Load optional accounts.
+    let optional_accounts = &mut optional_accounts.iter();
+    let tool_info = next_optional_account(optional_accounts, args, MineOptionalAccount::Tool)?;
+    let miner_stats_info = next_optional_account(optional_accounts, args, MineOptionalAccount::MinerStats)?;
+    let leaderboard_info = next_optional_account(optional_accounts, args, MineOptionalAccount::Leaderboard)?;
+    if leaderboard_info.is_some() && miner_stats_info.is_none() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let overflow_bus_info = next_optional_account(optional_accounts, args, MineOptionalAccount::OverflowBus)?;
+    let chromium_stake_info = next_optional_account(optional_accounts, args, MineOptionalAccount::ChromiumStake)?;
+    let delegation_info = next_optional_account(optional_accounts, args, MineOptionalAccount::Delegation)?;
 
     // Authenticate the proof account.
     //
@@ -53,16 +73,45 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     // Validate epoch is active.
     let mut config_data = config_info.data.borrow_mut();
     let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+    let resource_config_data = resource_config_info.data.borrow();
+    let resource_config = ResourceConfig::try_from_bytes(&resource_config_data)?;
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     if config
         .last_reset_at
-        .saturating_add(WOOD_EPOCH_DURATION)
+        .saturating_add(resource_config.epoch_duration)
         .le(&clock.unix_timestamp)
     {
         println!("Needs reset");
         return Err(CoalError::NeedsReset.into());
     }
 
+    // Authenticate the miner.
+    //
+    // The signer must be the proof's miner, or an unexpired delegate of the proof. Tools always belong
+    // to the proof's miner.
+    let proof = ProofV2::try_from_bytes(&proof_info.data.borrow())?.to_owned();
+    if proof.resource.ne(&WOOD_MINT_ADDRESS) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let miner = proof.miner;
+    authenticate_miner(signer, &miner, proof_info, delegation_info, tool_info.is_some(), clock.unix_timestamp)?;
+
+    // Validate the buses are in use.
+    //
+    // Buses beyond the configured bus count are not topped up at reset. The overflow bus must differ from
+    // the primary bus.
+    if Bus::try_from_bytes(&bus_info.data.borrow())?.id.ge(&resource_config.bus_count) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if let Some(overflow_bus_info) = overflow_bus_info {
+        load_any_wood_bus(overflow_bus_info, true)?;
+        if overflow_bus_info.key.eq(bus_info.key)
+            || Bus::try_from_bytes(&overflow_bus_info.data.borrow())?.id.ge(&resource_config.bus_count)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+
     // Validate the hash digest.
     //
     // Here we use drillx_2 to validate the provided solution is a valid hash of the challenge.
@@ -85,27 +134,23 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         return Err(CoalError::Spam.into());
     }
 
-    // Validate the hash satisfies the minimum difficulty.
+    // Validate the hash satisfies the difficulty target.
     //
-    // We use drillx_2 to get the difficulty (leading zeros) of the hash. If the hash does not have the
-    // minimum required difficulty, we reject it with an error.
+    // The target is a fractional threshold on the hash value, rather than a whole number of leading zeros.
+    // If the hash is not below the target, we reject it with an error.
     let hash = solution.to_hash();
     let difficulty = hash.difficulty();
-    if difficulty.lt(&(config.min_difficulty as u32)) {
+    let difficulty_target = resource_config.difficulty_target();
+    if !meets_difficulty_target(&hash.h, difficulty_target) {
         return Err(CoalError::HashTooEasy.into());
     }
 
-    // Normalize the difficulty and calculate the reward amount.
+    // Calculate the reward amount.
     //
-    // The reward doubles for every bit of difficulty (leading zeros) on the hash. We use the normalized
-    // difficulty so the minimum accepted difficulty pays out at the base reward rate.
-    let normalized_difficulty = difficulty
-        .checked_sub(config.min_difficulty as u32)
-        .unwrap();
-    let mut reward = config
-        .base_reward_rate
-        .checked_mul(2u64.checked_pow(normalized_difficulty).unwrap())
-        .unwrap();
+    // The reward doubles for every bit of difficulty on the hash. We use the fractional difficulty of the
+    // full hash value, so the reward grows smoothly with the hash and a hash exactly at the target pays out
+    // at the base reward rate.
+    let mut reward = calculate_hash_reward(config.base_reward_rate, &hash.h, difficulty_target);
 
     // Increment total hash power and apply staking multiplier.
     //
@@ -114,6 +159,14 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     // if the miner's last stake deposit was more than one minute ago to protect against flash loan attacks.
     let mut bus_data = bus_info.data.borrow_mut();
     let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+    let mut overflow_bus_data = overflow_bus_info.map(|info| info.data.borrow_mut());
+    let mut overflow_bus = overflow_bus_data
+        .as_mut()
+        .map(|data| Bus::try_from_bytes_mut(data))
+        .transpose()?;
+    let available_rewards = bus
+        .rewards
+        .saturating_add(overflow_bus.as_ref().map_or(0, |overflow_bus| overflow_bus.rewards));
     
     if proof.balance.gt(&0) && proof.last_stake_at.saturating_add(ONE_MINUTE).lt(&t) {
         // Calculate staking reward.
@@ -141,6 +194,7 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     // The penalty works by halving the reward amount for every minute late the solution has been submitted.
     // This ultimately drives the reward to zero given enough time (10-20 minutes).
     let t_liveness = t_target.saturating_add(WOOD_LIVENESS_TOLERANCE);
+    let reward_before_penalty = reward;
     if t.gt(&t_liveness) {
         // Halve the reward for every minute late.
         let tardiness = t.saturating_sub(t_target) as u64;
@@ -160,53 +214,70 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         }
     }
 
+    let late_penalty = reward_before_penalty.saturating_sub(reward);
+    let base_reward = reward;
+
     // Apply tool multiplier.
     //
     // Durability is decremented for the amount added.
 
     let mut tool_reward: u64 = 0;
 
-    if optional_accounts.len().ge(&1) {
-        let tool_info = &optional_accounts[0];
-
-        if !tool_info.data_is_empty() {
-            load_wood_tool(&tool_info, signer.key, true)?;
-    
-            let mut tool_data = tool_info.data.borrow_mut();
-            let tool = WoodTool::try_from_bytes_mut(&mut tool_data)?;
-
-            if tool.durability.gt(&0) {
-                // Calculate the additional reward.
-                let max_additional_reward = bus.rewards.saturating_sub(reward);
-                let additional_reward = (reward as u128)
-                    .checked_mul(tool.multiplier.max(BASE_TOOL_MULTIPLIER).min(MAX_TOOL_MULTIPLIER) as u128)
-                    .unwrap()
-                    .checked_div(100)
-                    .unwrap() as u64;
-                tool_reward = additional_reward.min(tool.durability);
-                msg!("tool_reward: {}", tool_reward.saturating_div(ONE_WOOD));
-                reward = reward.checked_add(tool_reward).unwrap();
-            
-                // Durability is decremented for the amount added.
-                // Only subtract the actual remaining rewards from durability.
-                let actual_additional_reward = tool_reward.min(max_additional_reward);
-                tool.durability = tool.durability.saturating_sub(actual_additional_reward).max(0);
-            }
+    if let Some(tool_info) = tool_info {
+        load_wood_tool(tool_info, &miner, true)?;
+
+        let mut tool_data = tool_info.data.borrow_mut();
+        let tool = WoodTool::try_from_bytes_mut(&mut tool_data)?;
+
+        if tool.durability.gt(&0) {
+            // Calculate the additional reward.
+            let max_additional_reward = available_rewards.saturating_sub(reward);
+            let additional_reward = (reward as u128)
+                .checked_mul(tool.multiplier.max(BASE_TOOL_MULTIPLIER).min(MAX_TOOL_MULTIPLIER) as u128)
+                .unwrap()
+                .checked_div(100)
+                .unwrap() as u64;
+            tool_reward = additional_reward.min(tool.durability);
+            msg!("tool_reward: {}", tool_reward.saturating_div(ONE_WOOD));
+            reward = reward.checked_add(tool_reward).unwrap();
+        
+            // Durability is decremented for the amount added.
+            // Only subtract the actual remaining rewards from durability.
+            let actual_additional_reward = tool_reward.min(max_additional_reward);
+            tool.durability = tool.durability.saturating_sub(actual_additional_reward).max(0);
         }
     }
 
-    // Limit payout amount to whatever is left in the bus.
+    // Apply CHROMIUM stake multiplier.
+    //
+    // The bonus is calculated on the base reward so it does not compound with the other multipliers.
+    if let Some(chromium_stake_info) = chromium_stake_info {
+        load_chromium_stake(chromium_stake_info, proof_info.key, false)?;
+        let chromium_reward = ChromiumStake::try_from_bytes(&chromium_stake_info.data.borrow())?.reward(base_reward, t);
+        msg!("chromium_reward: {}", chromium_reward.saturating_div(ONE_WOOD));
+        reward = reward.checked_add(chromium_reward).unwrap();
+    }
+
+    // Limit payout amount to whatever is left in the buses.
     //
     // Busses are limited to distributing n COAL per epoch. This is also the maximum amount that will be paid out
     // for any given hash.
-    let reward_actual = reward.min(bus.rewards);
+    let reward_actual = reward.min(available_rewards);
 
     // Update balances.
     //
     // We track the theoretical rewards that would have been paid out ignoring the bus limit, so the
     // base reward rate will be updated to account for the real hashpower on the network.
-    bus.theoretical_rewards = bus.theoretical_rewards.checked_add(reward).unwrap();
-    bus.rewards = bus.rewards.checked_sub(reward_actual).unwrap();
+    //
+    // The primary bus pays first and the overflow bus covers the remainder. The overflow bus records the
+    // theoretical rewards it paid out, so the buses together record the theoretical rewards of the hash once.
+    let overflow_reward = reward_actual.saturating_sub(bus.rewards);
+    if let Some(overflow_bus) = overflow_bus.as_mut() {
+        overflow_bus.theoretical_rewards = overflow_bus.theoretical_rewards.checked_add(overflow_reward).unwrap();
+        overflow_bus.rewards = overflow_bus.rewards.checked_sub(overflow_reward).unwrap();
+    }
+    bus.theoretical_rewards = bus.theoretical_rewards.checked_add(reward.saturating_sub(overflow_reward)).unwrap();
+    bus.rewards = bus.rewards.checked_sub(reward_actual.saturating_sub(overflow_reward)).unwrap();
     proof.balance = proof.balance.checked_add(reward_actual).unwrap();
 
     // Hash a recent slot hash into the next challenge to prevent pre-mining attacks.
@@ -226,21 +297,56 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
 
     // Update lifetime stats.
     proof.total_hashes = proof.total_hashes.saturating_add(1);
-    proof.total_rewards = proof.total_rewards.saturating_add(reward);
+    proof.total_rewards = proof.total_rewards.saturating_add(reward_actual);
+
+    // Update miner stats.
+    //
+    // Only the bonuses which fit under the payout limit are recorded.
+    let mut epoch_rewards = 0;
+    if let Some(miner_stats_info) = miner_stats_info {
+        load_miner_stats(miner_stats_info, proof_info.key, true)?;
+        let mut miner_stats_data = miner_stats_info.data.borrow_mut();
+        let miner_stats = MinerStats::try_from_bytes_mut(&mut miner_stats_data)?;
+        let (paid_tool_reward, _) = MinerStats::paid_bonuses(base_reward, reward_actual, tool_reward, 0);
+        miner_stats.record_hash(
+            config.last_reset_at,
+            difficulty as u64,
+            reward_actual,
+            late_penalty,
+            paid_tool_reward,
+            0,
+        );
+        epoch_rewards = miner_stats.epoch_rewards;
+    }
+
+    // Update the bus leaderboard.
+    //
+    // Proofs are ranked by their rewards in the current epoch, as tracked by the miner stats. A leaderboard
+    // which still holds the previous epoch is left for the merge crank to rotate, unless it is empty.
+    if let Some(leaderboard_info) = leaderboard_info {
+        load_leaderboard(leaderboard_info, &WOOD_MINT_ADDRESS, bus_info.key, true)?;
+        let mut leaderboard_data = leaderboard_info.data.borrow_mut();
+        let leaderboard = Leaderboard::try_from_bytes_mut(&mut leaderboard_data)?;
+        if leaderboard.epoch_start_at.ne(&config.last_reset_at) && leaderboard.is_empty() {
+            leaderboard.rotate(config.last_reset_at);
+        }
+        if leaderboard.epoch_start_at.eq(&config.last_reset_at) {
+            leaderboard.record(*proof_info.key, epoch_rewards);
+        }
+    }
 
     // Log the mined rewards.
     //
     // This data can be used by off-chain indexers to display mining stats.
-    set_return_data(
-        MineEvent {
-            difficulty: difficulty as u64,
-            reward: reward_actual,
-            timing: t.saturating_sub(t_liveness),
-            tool_reward,
-            stake_reward: 0,
-        }
-        .to_bytes(),
-    );
+    let event = MineEvent {
+        difficulty: difficulty as u64,
+        reward: reward_actual,
+        timing: t.saturating_sub(t_liveness),
+        tool_reward,
+        stake_reward: 0,
+    };
+    set_return_data(event.to_bytes());
+    event.log();
 
     Ok(())
 }
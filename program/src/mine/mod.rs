@@ -1,11 +1,17 @@
-use coal_api::state::{Config, WoodConfig};
+use coal_api::{
+    error::CoalError,
+    instruction::{MineArgsV2, MineOptionalAccount},
+    loaders::load_delegation,
+    state::{Config, DelegateRestriction, Delegation, WoodConfig},
+};
 use solana_program::{
-    account_info::AccountInfo, 
+    account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     program_error::ProgramError,
+    pubkey::Pubkey,
 };
 
-use crate::utils::Discriminator;
+use crate::utils::{AccountDeserialize, Discriminator};
 
 use chop_wood::*;
 use mine_coal::*;
@@ -24,4 +30,47 @@ pub fn process_mine<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8])
     }
 
     return Err(ProgramError::InvalidAccountData);    
-}
\ No newline at end of file
+}
+
+/// Returns the next optional account if the mine args declare it.
+pub(crate) fn next_optional_account<'a, 'info>(
+    iter: &mut std::slice::Iter<'a, AccountInfo<'info>>,
+    args: &MineArgsV2,
+    account: MineOptionalAccount,
+) -> Result<Option<&'a AccountInfo<'info>>, ProgramError> {
+    if !args.has(account) {
+        return Ok(None);
+    }
+
+    Ok(Some(next_account_info(iter)?))
+}
+
+/// Authenticates the signer as the proof's miner, or as an unexpired delegate of the proof. Delegates
+/// restricted from using tools cannot mine with one.
+pub(crate) fn authenticate_miner(
+    signer: &AccountInfo<'_>,
+    miner: &Pubkey,
+    proof_info: &AccountInfo<'_>,
+    delegation_info: Option<&AccountInfo<'_>>,
+    uses_tool: bool,
+    now: i64,
+) -> Result<(), ProgramError> {
+    if signer.key.eq(miner) {
+        return Ok(());
+    }
+
+    let Some(delegation_info) = delegation_info else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+    load_delegation(delegation_info, proof_info.key, false)?;
+    let delegation_data = delegation_info.data.borrow();
+    let delegation = Delegation::try_from_bytes(&delegation_data)?;
+    let delegate = delegation
+        .find(signer.key, now)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    if uses_tool && delegate.is_restricted(DelegateRestriction::NoTool) {
+        return Err(CoalError::DelegateRestricted.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    event::OpenMinerStatsEvent,
+    instruction::OpenArgs,
+    loaders::*,
+    state::{MinerStats, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+
+/// Open miner stats creates an account to track extended mining stats for a proof.
+pub fn process_open_miner_stats(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = OpenArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, payer_info, proof_info, miner_stats_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_signer(payer_info)?;
+    if proof_info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if proof_info.data.borrow()[0].eq(&Proof::discriminator()) {
+        load_coal_proof(proof_info, signer.key, false)?;
+    } else {
+        load_proof_v2(proof_info, signer.key, &WOOD_MINT_ADDRESS, false)?;
+    }
+    load_uninitialized_pda(
+        miner_stats_info,
+        &[MINER_STATS, proof_info.key.as_ref()],
+        args.bump,
+        &coal_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+
+    // Initialize miner stats.
+    create_pda(
+        miner_stats_info,
+        &coal_api::id(),
+        8 + size_of::<MinerStats>(),
+        &[MINER_STATS, proof_info.key.as_ref(), &[args.bump]],
+        system_program,
+        payer_info,
+    )?;
+    let mut miner_stats_data = miner_stats_info.data.borrow_mut();
+    miner_stats_data[0] = MinerStats::discriminator();
+    let miner_stats = MinerStats::try_from_bytes_mut(&mut miner_stats_data)?;
+    miner_stats.proof = *proof_info.key;
+
+    // Log the open.
+    OpenMinerStatsEvent {
+        authority: *signer.key,
+        proof: *proof_info.key,
+    }
+    .log();
+
+    Ok(())
+}
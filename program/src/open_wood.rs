@@ -1,6 +1,6 @@
 use std::mem::size_of;
 
-use coal_api::{consts::*, instruction::OpenArgs, loaders::*, state::ProofV2};
+use coal_api::{consts::*, event::OpenEvent, instruction::OpenArgs, loaders::*, state::ProofV2};
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, keccak::hashv, program_error::ProgramError, slot_hashes::SlotHash, system_program, sysvar::{self, Sysvar}
 };
@@ -58,5 +58,13 @@ pub fn process_open_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[
     proof.total_hashes = 0;
     proof.total_rewards = 0;
 
+    // Log the open.
+    OpenEvent {
+        authority: *signer.key,
+        resource: WOOD_MINT_ADDRESS,
+        miner: *miner_info.key,
+    }
+    .log();
+
     Ok(())
 }
@@ -0,0 +1,157 @@
+use coal_api::{
+    error::CoalError,
+    event::CancelReprocessEvent,
+    loaders::*,
+    state::{MinerStats, Reprocessor},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+use super::reprocess::proof_stats;
+
+/// Cancels a reprocess and refunds the rent of the reprocessor. The reprocess fee is kept by the treasury.
+///
+/// A reprocess can only be cancelled while finalizing it is not worthwhile. Before the target slot the
+/// outcome is unknown to everyone, so the stats of the proof are left untouched. Once the slot hash has
+/// expired the authority knew the outcome, so cancelling consumes the unreprocessed stats of the proof without
+/// a reward. Otherwise a bad outcome could be skipped and the same stats rolled again for the reprocess fee.
+///
+/// Legacy reprocessors can no longer be finalized, so they are closed without touching any stats.
+pub fn process_cancel_reprocess(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, reprocessor_info, proof_info, miner_stats_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+
+    // Close a legacy reprocessor of the signer.
+    if is_legacy_reprocessor(reprocessor_info, signer.key) {
+        if !reprocessor_info.is_writable {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        return close_reprocessor(signer, reprocessor_info, Pubkey::default(), 0, 0);
+    }
+    load_reprocessor(reprocessor_info, signer.key, true)?;
+
+    // Validate the reprocess is not worth finalizing.
+    let reprocessor_data = reprocessor_info.data.borrow();
+    let reprocessor = Reprocessor::try_from_bytes(&reprocessor_data)?;
+    let current_slot = Clock::get()?.slot;
+    if reprocessor.is_worthwhile(current_slot) {
+        return Err(CoalError::ReprocessActive.into());
+    }
+    if reprocessor.proof.ne(proof_info.key) || reprocessor.miner_stats.ne(miner_stats_info.key) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let is_revealed = current_slot.gt(&reprocessor.slot);
+    drop(reprocessor_data);
+
+    // Consume the stats of the proof once the outcome is known.
+    //
+    // The miner stats existed when the reprocess was initialized, so they must still be provided.
+    let (mut hashes, mut rewards) = (0, 0);
+    if is_revealed {
+        load_miner_stats(miner_stats_info, proof_info.key, true)?;
+        let (lifetime_hashes, lifetime_rewards) = proof_stats(proof_info)?;
+        let mut miner_stats_data = miner_stats_info.data.borrow_mut();
+        let miner_stats = MinerStats::try_from_bytes_mut(&mut miner_stats_data)?;
+        (hashes, rewards) = miner_stats.unreprocessed(lifetime_hashes, lifetime_rewards);
+        miner_stats.record_reprocess(lifetime_hashes, lifetime_rewards, 0, 0);
+    }
+
+    close_reprocessor(signer, reprocessor_info, *proof_info.key, hashes, rewards)
+}
+
+/// Returns whether the account is a legacy reprocessor of the given authority.
+fn is_legacy_reprocessor(info: &AccountInfo<'_>, authority: &Pubkey) -> bool {
+    if info.owner.ne(&coal_api::id()) || info.data_len().ne(&Reprocessor::LEGACY_SIZE) {
+        return false;
+    }
+    let data = info.data.borrow();
+    data[0].eq(&Reprocessor::discriminator()) && data[8..40].eq(authority.as_ref())
+}
+
+/// Closes the reprocessor, refunds its lamports to the signer and logs the cancellation.
+fn close_reprocessor(
+    signer: &AccountInfo<'_>,
+    reprocessor_info: &AccountInfo<'_>,
+    proof: Pubkey,
+    hashes: u64,
+    rewards: u64,
+) -> ProgramResult {
+    // Realloc data to zero.
+    reprocessor_info.realloc(0, true)?;
+
+    // Send remaining lamports to signer.
+    **signer.lamports.borrow_mut() += reprocessor_info.lamports();
+    **reprocessor_info.lamports.borrow_mut() = 0;
+
+    // Log the cancellation.
+    CancelReprocessEvent {
+        authority: *signer.key,
+        proof,
+        hashes,
+        rewards,
+    }
+    .log();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use coal_api::consts::REPROCESSOR;
+    use solana_program::system_program;
+
+    use crate::test_utils::{account_infos, serialize, TestAccount};
+
+    use super::*;
+
+    fn accounts(signer: Pubkey, authority: Pubkey) -> Vec<TestAccount> {
+        let mut data = vec![0; Reprocessor::LEGACY_SIZE];
+        data[0] = Reprocessor::discriminator();
+        data[8..40].copy_from_slice(authority.as_ref());
+        let reprocessor = Pubkey::find_program_address(&[REPROCESSOR, authority.as_ref()], &coal_api::id()).0;
+        let account = |key, owner, lamports, data, is_signer| TestAccount {
+            key,
+            owner,
+            lamports,
+            data,
+            is_signer,
+            is_writable: true,
+        };
+        vec![
+            account(signer, system_program::id(), 0, vec![], true),
+            account(reprocessor, coal_api::id(), 1_000, data, false),
+            account(Pubkey::new_unique(), coal_api::id(), 0, vec![], false),
+            account(Pubkey::new_unique(), coal_api::id(), 0, vec![], false),
+        ]
+    }
+
+    #[test]
+    fn test_cancel_legacy_reprocessor() {
+        let authority = Pubkey::new_unique();
+        let mut buffer = serialize(&accounts(authority, authority));
+        let infos = account_infos(&mut buffer);
+        process_cancel_reprocess(&infos, &[]).unwrap();
+        assert_eq!(infos[0].lamports(), 1_000);
+        assert_eq!(infos[1].lamports(), 0);
+        assert_eq!(infos[1].data_len(), 0);
+    }
+
+    #[test]
+    fn test_cancel_legacy_reprocessor_of_other_authority() {
+        let mut buffer = serialize(&accounts(Pubkey::new_unique(), Pubkey::new_unique()));
+        let infos = account_infos(&mut buffer);
+        assert!(process_cancel_reprocess(&infos, &[]).is_err());
+        assert_eq!(infos[1].lamports(), 1_000);
+    }
+}
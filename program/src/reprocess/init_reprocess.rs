@@ -1,27 +1,27 @@
 use std::mem::size_of;
 
-use coal_api::{consts::*, instruction::ReprocessArgs, loaders::*, state::Reprocessor};
+use coal_api::{consts::*, event::InitReprocessEvent, instruction::InitReprocessArgs, loaders::*, state::{Reprocessor, Treasury}};
 use solana_program::{
     account_info::AccountInfo,
     clock::Clock,
     entrypoint::ProgramResult,
-    keccak::hashv,
     program::invoke,
     program_error::ProgramError,
-    slot_hashes::SlotHash, 
     system_instruction::transfer, 
-    sysvar::{self, Sysvar}
+    sysvar::Sysvar,
 };
 
-use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+use crate::utils::{create_pda, realloc_pda, AccountDeserialize, Discriminator};
+
+use super::reprocess::load_reprocess_proof;
 
 
 pub fn process_initialize_reprocess(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     // Parse args.
-    let args = ReprocessArgs::try_from_bytes(data)?;
+    let args = InitReprocessArgs::try_from_bytes(data)?;
 
     // Load accounts.
-    let [signer, treasury_info, reprocessor_info, slot_hashes_sysvar, system_program] =
+    let [signer, treasury_info, reprocessor_info, system_program, proof_info, miner_stats_info] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -34,7 +34,11 @@ pub fn process_initialize_reprocess(accounts: &[AccountInfo], data: &[u8]) -> Pr
         args.reprocessor_bump,
         &coal_api::id(),
     )?;
-    load_sysvar(slot_hashes_sysvar, sysvar::slot_hashes::id())?;
+    load_reprocess_proof(proof_info, signer.key)?;
+
+    // The miner stats are required up front. Otherwise they could be opened after the outcome is known, only
+    // if the outcome is good.
+    load_miner_stats(miner_stats_info, proof_info.key, false)?;
 
     // Initialize reprocessor.
     create_pda(
@@ -50,14 +54,24 @@ pub fn process_initialize_reprocess(accounts: &[AccountInfo], data: &[u8]) -> Pr
     reprocessor_data[0] = Reprocessor::discriminator() as u8;
     let reprocessor = Reprocessor::try_from_bytes_mut(&mut reprocessor_data)?;
     reprocessor.authority = *signer.key;
+    reprocessor.proof = *proof_info.key;
+    reprocessor.miner_stats = *miner_stats_info.key;
     
     let slot = Clock::get()?.slot;
     reprocessor.slot = slot + REPROCESS_TARGET_SLOT;
-    reprocessor.hash = hashv(&[
-        &slot_hashes_sysvar.data.borrow()[0..size_of::<SlotHash>()],
-    ])
-    .0;
-    
+    reprocessor.commitment = args.commitment;
+    reprocessor.keeper = args.keeper;
+    reprocessor.keeper_tip = u64::from_le_bytes(args.keeper_tip);
+    let keeper_tip = reprocessor.keeper_tip;
+    let target_slot = reprocessor.slot;
+    drop(reprocessor_data);
+
+    // Record the fee in the treasury.
+    realloc_pda(treasury_info, 8 + size_of::<Treasury>(), system_program, signer)?;
+    let mut treasury_data = treasury_info.data.borrow_mut();
+    let treasury = Treasury::try_from_bytes_mut(&mut treasury_data)?;
+    treasury.fees_collected = treasury.fees_collected.saturating_add(REPROCESS_FEE);
+    drop(treasury_data);
 
     // Transfer fee of 0.005 SOL to treasury
     // This is to discourage abuse
@@ -75,5 +89,24 @@ pub fn process_initialize_reprocess(accounts: &[AccountInfo], data: &[u8]) -> Pr
         ],
     )?;
 
+    // Deposit the keeper tip into the reprocessor.
+    if keeper_tip.gt(&0) {
+        invoke(
+            &transfer(signer.key, reprocessor_info.key, keeper_tip),
+            &[
+                signer.clone(),
+                reprocessor_info.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    // Log the reprocess initialization.
+    InitReprocessEvent {
+        authority: *signer.key,
+        target_slot,
+    }
+    .log();
+
     Ok(())
 }
\ No newline at end of file
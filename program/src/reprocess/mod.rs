@@ -1,2 +1,3 @@
 pub mod reprocess;
 pub mod init_reprocess;
+pub mod cancel_reprocess;
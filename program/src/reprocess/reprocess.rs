@@ -3,8 +3,10 @@ use std::mem::size_of;
 use coal_api::{
     consts::*,
     error::CoalError,
+    event::ReprocessEvent,
+    instruction::ReprocessArgs,
     loaders::*,
-    state::{Bus, Proof, Reprocessor}
+    state::{ChromiumConfig, MinerStats, Proof, ProofV2, Reprocessor, BPS_DENOMINATOR}
 };
 use solana_program::{
     account_info::AccountInfo,
@@ -12,30 +14,53 @@ use solana_program::{
     entrypoint::ProgramResult, 
     msg,
     program_error::ProgramError, 
+    program_pack::Pack,
+    pubkey::Pubkey,
     slot_hashes::SlotHash, 
     sysvar::{self, Sysvar},
     keccak::hashv
 };
 
-use crate::utils::AccountDeserialize;
+use spl_token::state::Mint;
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+pub fn process_reprocess(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = ReprocessArgs::try_from_bytes(data)?;
 
-pub fn process_reprocess(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer, reprocessor_info, proof_info, bus_info, mint_info, tokens_info, treasury_info, token_program, slot_hashes_sysvar] =
+    let [signer, authority_info, reprocessor_info, proof_info, miner_stats_info, chromium_config_info, mint_info, tokens_info, treasury_info, token_program, slot_hashes_sysvar] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
     load_signer(signer)?;
-    load_coal_bus(bus_info, 0, false)?;
-    load_coal_proof(proof_info, signer.key, true)?;
-    load_reprocessor(reprocessor_info, signer.key, true)?;
+    if !authority_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let resource = load_reprocess_proof(proof_info, authority_info.key)?;
+    load_miner_stats(miner_stats_info, proof_info.key, true)?;
+    load_reprocessor(reprocessor_info, authority_info.key, true)?;
+    load_token_account(tokens_info, Some(authority_info.key), &CHROMIUM_MINT_ADDRESS, true)?;
     load_sysvar(slot_hashes_sysvar, sysvar::slot_hashes::id())?;
+    load_chromium_config(chromium_config_info, true)?;
+    load_mint(mint_info, CHROMIUM_MINT_ADDRESS, true)?;
 
     
     let mut reprocessor_data = reprocessor_info.data.borrow_mut();
     let reprocessor = Reprocessor::try_from_bytes_mut(&mut reprocessor_data)?;
+
+    // Only the authority or its keeper can finalize.
+    if !reprocessor.can_finalize(signer.key) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only the proof chosen when the reprocess was initialized can be reprocessed.
+    if reprocessor.proof.ne(proof_info.key) || reprocessor.miner_stats.ne(miner_stats_info.key) {
+        return Err(ProgramError::InvalidAccountData);
+    }
     
     // Target slot is 20 slots ahead of the starting slot
     let target_slot = reprocessor.slot;
@@ -46,32 +71,40 @@ pub fn process_reprocess(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResul
         return Err(CoalError::SlotTooEarly.into());
     }
     
-    let mut proof_data = proof_info.data.borrow_mut();
-    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
-    let total_hashes = proof.total_hashes;
-    let total_rewards = proof.total_rewards;
+    // Calculate the hashes and rewards since the last reprocess.
+    //
+    // The lifetime stats of the proof are left untouched. The miner stats track how much of them has
+    // already been reprocessed.
+    let (lifetime_hashes, lifetime_rewards) = proof_stats(proof_info)?;
+    let mut miner_stats_data = miner_stats_info.data.borrow_mut();
+    let miner_stats = MinerStats::try_from_bytes_mut(&mut miner_stats_data)?;
+    let (total_hashes, total_rewards) = miner_stats.unreprocessed(lifetime_hashes, lifetime_rewards);
 
     if total_hashes.eq(&0) || total_rewards.eq(&0) {
         return Err(CoalError::Spam.into())
     }
 
-    // IMPORTANT: Reset total hashes and rewards to 0
-    proof.total_hashes = 0;
-    proof.total_rewards = 0;
+    // Verify the revealed secret matches the commitment.
+    if hashv(&[&args.secret]).0.ne(&reprocessor.commitment) {
+        return Err(CoalError::InvalidReveal.into());
+    }
 
     // Calculate the final hash
-    let bus_data = bus_info.data.borrow();
-    let bus = Bus::try_from_bytes(&bus_data)?;
-    let final_hash = hashv(&[
-        &reprocessor.hash,
-        &bus.rewards.to_le_bytes(),
-        &slot_hashes_sysvar.data.borrow()[0..size_of::<SlotHash>()],
-    ])
-    .0;
+    //
+    // The secret is mixed with the hash of the target slot. The secret was committed before the target slot
+    // was produced and the slot leader never learns it, so neither can predict the result.
+    let slot_hash = find_slot_hash(&slot_hashes_sysvar.data.borrow(), target_slot)
+        .ok_or(CoalError::SlotTooLate)?;
+    let final_hash = hashv(&[&args.secret, &slot_hash]).0;
 
     // Derive a number between 1 and 100 from the final hash
     let mut pseudo_random_number = derive_number_from_hash(&final_hash, 1, REPROCESS_MAX_MULTIPLIER);
-    let reward = calculate_reward(total_hashes, total_rewards);
+    let weight = match resource {
+        COAL_MINT_ADDRESS => COAL_REPROCESS_WEIGHT,
+        WOOD_MINT_ADDRESS => WOOD_REPROCESS_WEIGHT,
+        _ => return Err(CoalError::InvalidResource.into()),
+    };
+    let reward = calculate_reward(total_hashes, total_rewards, weight);
 
     // Calculate the liveness penalty
     let s_tolerance = target_slot.saturating_add(REPROCESS_SLOT_BUFFER);
@@ -81,57 +114,156 @@ pub fn process_reprocess(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResul
     
     if current_slot.gt(&s_tolerance) {
         // Halve the reward for every slot late.
-        let halvings = current_slot.saturating_sub(s_tolerance) as u64;
+        let halvings = reprocessor.halvings(current_slot);
         msg!("Halvings: {}", halvings);
         if halvings.gt(&0) {
             pseudo_random_number = pseudo_random_number.saturating_div(2u64.saturating_pow(halvings as u32)).max(1);
         }
     }
 
-    let actual_reward = apply_randomness(reward, pseudo_random_number);
+    // Limit the reward by the CHROMIUM supply policy.
+    //
+    // If nothing can be minted, the reprocess cannot be finalized. Otherwise the stats would be kept for any
+    // outcome, and a bad outcome could be skipped for the reprocess fee. Whether anything can be minted does
+    // not depend on the outcome.
+    let mut chromium_config_data = chromium_config_info.data.borrow_mut();
+    let chromium_config = ChromiumConfig::try_from_bytes_mut(&mut chromium_config_data)?;
+    chromium_config.rotate(Clock::get()?.unix_timestamp);
+    let mint = Mint::unpack(&mint_info.data.borrow())?;
+    if chromium_config.available(mint.supply).eq(&0) {
+        return Err(CoalError::MintCapReached.into());
+    }
+    let requested_reward = apply_randomness(reward, pseudo_random_number);
+    let scaled_reward = chromium_config.scale(requested_reward);
+    let actual_reward = chromium_config.mint(requested_reward, mint.supply);
     msg!("Actual reward: {}", actual_reward);
+
+    // Record the reprocess.
+    //
+    // Only the share of the stats whose reward was minted is consumed. The share cut by the epoch mint cap or
+    // the max supply can be reprocessed in a later epoch.
+    miner_stats.record_reprocess(lifetime_hashes, lifetime_rewards, actual_reward, scaled_reward);
+    drop(miner_stats_data);
+
     // Mint chromium rewards
-    solana_program::program::invoke_signed(
-        &spl_token::instruction::mint_to(
-            &spl_token::id(),
-            mint_info.key,
-            tokens_info.key,
-            treasury_info.key,
-            &[treasury_info.key],
-            actual_reward,
-        )?,
-        &[
-            token_program.clone(),
-            mint_info.clone(),
-            tokens_info.clone(),
-            treasury_info.clone(),
-        ],
-        &[&[TREASURY, &[TREASURY_BUMP]]],
-    )?;
+    if actual_reward.gt(&0) {
+        solana_program::program::invoke_signed(
+            &spl_token::instruction::mint_to(
+                &spl_token::id(),
+                mint_info.key,
+                tokens_info.key,
+                treasury_info.key,
+                &[treasury_info.key],
+                actual_reward,
+            )?,
+            &[
+                token_program.clone(),
+                mint_info.clone(),
+                tokens_info.clone(),
+                treasury_info.clone(),
+            ],
+            &[&[TREASURY, &[TREASURY_BUMP]]],
+        )?;
+    }
 
+    let keeper_tip = reprocessor.keeper_tip;
     drop(reprocessor_data);
 
     // Realloc data to zero.
     reprocessor_info.realloc(0, true)?;
 
-    // Send remaining lamports to signer.
-    **signer.lamports.borrow_mut() += reprocessor_info.lamports();
+    // Pay the tip to the keeper, if it finalized.
+    if signer.key.ne(authority_info.key) {
+        let tip = keeper_tip.min(reprocessor_info.lamports());
+        **signer.lamports.borrow_mut() += tip;
+        **reprocessor_info.lamports.borrow_mut() -= tip;
+    }
+
+    // Send remaining lamports to authority.
+    **authority_info.lamports.borrow_mut() += reprocessor_info.lamports();
     **reprocessor_info.lamports.borrow_mut() = 0;
 
+    // Log the reprocess.
+    ReprocessEvent {
+        authority: *authority_info.key,
+        hashes: total_hashes,
+        rewards: total_rewards,
+        multiplier: pseudo_random_number,
+        reward: actual_reward,
+    }
+    .log();
+
     Ok(())
 
 }
 
-// Helper function to derive a number from the entire hash
+/// Loads the proof to reprocess and returns its resource. COAL proofs predate `ProofV2`, so both proof
+/// layouts are accepted.
+pub(crate) fn load_reprocess_proof(proof_info: &AccountInfo<'_>, authority: &Pubkey) -> Result<Pubkey, ProgramError> {
+    if proof_info.owner.ne(&coal_api::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    let discriminator = *proof_info.data.borrow().first().ok_or(ProgramError::UninitializedAccount)?;
+    if discriminator.eq(&Proof::discriminator()) {
+        load_coal_proof(proof_info, authority, false)?;
+        return Ok(COAL_MINT_ADDRESS);
+    }
+    let resource = ProofV2::try_from_bytes(&proof_info.data.borrow())?.resource;
+    load_proof_v2(proof_info, authority, &resource, false)?;
+    Ok(resource)
+}
+
+/// Returns the lifetime hashes and rewards of a proof.
+pub(crate) fn proof_stats(proof_info: &AccountInfo<'_>) -> Result<(u64, u64), ProgramError> {
+    let proof_data = proof_info.data.borrow();
+    if proof_data[0].eq(&Proof::discriminator()) {
+        let proof = Proof::try_from_bytes(&proof_data)?;
+        Ok((proof.total_hashes, proof.total_rewards))
+    } else {
+        let proof = ProofV2::try_from_bytes(&proof_data)?;
+        Ok((proof.total_hashes, proof.total_rewards))
+    }
+}
+
+/// Returns the hash of the first slot at or after the given slot, as long as the slot hashes sysvar still
+/// covers the slot. Skipped slots have no hash, so the next produced slot is used instead.
+fn find_slot_hash(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let len = u64::from_le_bytes(data.get(0..8)?.try_into().ok()?) as usize;
+    let mut slot_hash = None;
+    for entry in data.get(8..)?.chunks_exact(size_of::<SlotHash>()).take(len) {
+        // Entries are sorted from the most recent slot to the oldest.
+        let entry_slot = u64::from_le_bytes(entry[0..8].try_into().ok()?);
+        if entry_slot.lt(&slot) {
+            return slot_hash;
+        }
+        slot_hash = Some(entry[8..40].try_into().ok()?);
+        if entry_slot.eq(&slot) {
+            return slot_hash;
+        }
+    }
+    None
+}
+
+/// Derives a uniformly distributed number between min and max (inclusive) from a hash.
+///
+/// Each 8 byte chunk of the hash is tried in turn. Chunks which fall into the incomplete range at the top
+/// of u64 are rejected to avoid a modulo bias, and the hash is rehashed if every chunk is rejected.
 fn derive_number_from_hash(hash: &[u8; 32], min: u64, max: u64) -> u64 {
-    let mut acc = 0u64;
-    for chunk in hash.chunks(8) {
-        acc = acc.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap_or([0; 8])));
+    let range = max - min + 1;
+    let zone = u64::MAX - (u64::MAX - range + 1) % range;
+    let mut hash = *hash;
+    loop {
+        for chunk in hash.chunks_exact(8) {
+            let n = u64::from_le_bytes(chunk.try_into().unwrap());
+            if n.le(&zone) {
+                return min + n % range;
+            }
+        }
+        hash = hashv(&[&hash]).0;
     }
-    min + (acc % (max - min + 1))
 }
 
-fn calculate_reward(total_hashes: u64, total_rewards: u64) -> u64 {
+fn calculate_reward(total_hashes: u64, total_rewards: u64, weight: u64) -> u64 {
     // Calculate a hash factor (gives more weight to number of hashes)
     let scaling_factor = 16u64;
     let hash_factor = scaling_factor.saturating_mul(total_hashes);
@@ -139,8 +271,10 @@ fn calculate_reward(total_hashes: u64, total_rewards: u64) -> u64 {
     // Calculate a reward factor (gives some weight to total rewards)
     let reward_factor = (total_rewards as f64).cbrt() as u64;
     
-    // Combine factors
-    let combined_factor = hash_factor.saturating_mul(reward_factor);
+    // Combine factors, weighted by the resource of the proof
+    let combined_factor = (hash_factor.saturating_mul(reward_factor) as u128)
+        .saturating_mul(weight as u128)
+        .saturating_div(BPS_DENOMINATOR as u128) as u64;
 
     // Logs
     msg!("Total hashes: {}", total_hashes);
@@ -153,4 +287,41 @@ fn calculate_reward(total_hashes: u64, total_rewards: u64) -> u64 {
 fn apply_randomness(base_reward: u64, pseudo_random_number: u64) -> u64 {
     msg!("Derived number: {}", pseudo_random_number);
     base_reward.saturating_mul(pseudo_random_number)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*slot as u8; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_find_slot_hash() {
+        let data = slot_hashes(&[105, 104, 102, 101]);
+        assert_eq!(find_slot_hash(&data, 104), Some([104; 32]));
+        // Skipped slots use the next produced slot.
+        assert_eq!(find_slot_hash(&data, 103), Some([104; 32]));
+        // Slots no longer covered by the sysvar are not found.
+        assert_eq!(find_slot_hash(&data, 100), None);
+        assert_eq!(find_slot_hash(&data, 106), None);
+    }
+
+    #[test]
+    fn test_derive_number_from_hash() {
+        for i in 0..=255u8 {
+            let n = derive_number_from_hash(&hashv(&[&[i]]).0, 1, REPROCESS_MAX_MULTIPLIER);
+            assert!(n.ge(&1) && n.le(&REPROCESS_MAX_MULTIPLIER));
+        }
+        // Chunks in the biased zone are rejected.
+        let mut hash = [u8::MAX; 32];
+        hash[24..32].copy_from_slice(&7u64.to_le_bytes());
+        assert_eq!(derive_number_from_hash(&hash, 1, 100), 8);
+    }
+}
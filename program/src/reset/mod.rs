@@ -1,11 +1,14 @@
-use coal_api::state::{Config, WoodConfig};
+use coal_api::{
+    loaders::load_resource_config,
+    state::{Config, ResourceConfig, WoodConfig},
+};
 use solana_program::{
     account_info::AccountInfo, 
     entrypoint::ProgramResult,
     program_error::ProgramError,
 };
 
-use crate::utils::Discriminator;
+use crate::utils::{AccountDeserialize, Discriminator};
 
 use reset_coal::*;
 use reset_wood::*;
@@ -14,7 +17,7 @@ mod reset_coal;
 mod reset_wood;
 
 pub fn process_reset<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
-    let config_info = &accounts[9];
+    let config_info = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
 
     if config_info.data.borrow()[0].eq(&(Config::discriminator() as u8)) {
         return process_reset_coal(accounts, data)
@@ -27,6 +30,57 @@ pub fn process_reset<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8])
     return Err(ProgramError::InvalidAccountData);    
 }
 
+/// The number of accounts of a resource passed to reset, excluding the signer and the buses.
+const RESET_ACCOUNTS_LEN: usize = 8;
+
+/// The position of the mint among the accounts of a resource passed to reset.
+const RESET_MINT_INDEX: usize = 1;
+
+/// The position of the resource config among the accounts of a resource passed to reset.
+const RESET_RESOURCE_CONFIG_INDEX: usize = 6;
+
+/// Resets every resource whose epoch has ended. The signer is followed by the accounts of each resource,
+/// in the same order as a reset of that resource. Resources which are not due yet are left unchanged.
+///
+/// Resetting COAL and WOOD together is supported up to `MAX_BUS_COUNT` buses each, which references 48
+/// accounts. With the default 8 buses each, it references 32 accounts.
+pub fn process_reset_all(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let [signer, resource_infos @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if resource_infos.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let mut resource_infos = resource_infos;
+
+    while !resource_infos.is_empty() {
+        // The number of buses of the resource is read from its resource config, which must belong to the
+        // mint of the resource. The mint itself is validated by reset.
+        let mint_info = resource_infos
+            .get(RESET_MINT_INDEX)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let resource_config_info = resource_infos
+            .get(RESET_RESOURCE_CONFIG_INDEX)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        load_resource_config(resource_config_info, mint_info.key, true)?;
+        let bus_count = ResourceConfig::try_from_bytes(&resource_config_info.data.borrow())?.bus_count as usize;
+        let len = bus_count.saturating_add(RESET_ACCOUNTS_LEN);
+        if resource_infos.len().lt(&len) {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (reset_infos, rest) = resource_infos.split_at(len);
+
+        let mut reset_accounts = Vec::with_capacity(len + 1);
+        reset_accounts.push(signer.clone());
+        reset_accounts.extend_from_slice(reset_infos);
+        process_reset(&reset_accounts, data)?;
+        resource_infos = rest;
+    }
+
+    Ok(())
+}
+
 /// This function calculates what the new reward rate should be based on how many total rewards
 /// were mined in the prior epoch. The math is largely identitical to function used by the Bitcoin
 /// network to update the difficulty between each epoch.
@@ -1,42 +1,52 @@
 use coal_api::{
     consts::*,
+    difficulty::{adjust_difficulty_target, min_difficulty},
+    emission::{rebalance_bus_rewards, EmissionEpoch},
+    event::ResetEvent,
     loaders::*,
-    state::{Bus, WoodConfig},
+    state::{Bus, EpochHistory, EpochSummary, ResourceConfig, WoodConfig},
 };
 use coal_utils::AccountDeserialize;
 use solana_program::{
-    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, program_error::ProgramError, sysvar::Sysvar
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, program_error::ProgramError, program_pack::Pack, sysvar::Sysvar
 };
+use spl_token::state::Mint;
 
 use crate::calculate_new_reward_rate;
 
 pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data: &[u8]) -> ProgramResult {
     msg!("Processing reset for WOOD");
     // Load accounts.
-    let [signer, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
+    let [signer, config_info, mint_info, treasury_info, treasury_tokens_info, token_program, epoch_history_info, resource_config_info, bounty_tokens_info, busses @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
-    load_wood_bus(bus_0_info, 0, true)?;
-    load_wood_bus(bus_1_info, 1, true)?;
-    load_wood_bus(bus_2_info, 2, true)?;
-    load_wood_bus(bus_3_info, 3, true)?;
-    load_wood_bus(bus_4_info, 4, true)?;
-    load_wood_bus(bus_5_info, 5, true)?;
-    load_wood_bus(bus_6_info, 6, true)?;
-    load_wood_bus(bus_7_info, 7, true)?;
     load_wood_config(config_info, true)?;
     load_mint(mint_info, WOOD_MINT_ADDRESS, true)?;
     load_treasury(treasury_info, true)?;
     load_wood_treasury_tokens(treasury_tokens_info, true)?;
     load_program(token_program, spl_token::id())?;
+    load_epoch_history(epoch_history_info, &WOOD_MINT_ADDRESS, true)?;
+    load_resource_config(resource_config_info, &WOOD_MINT_ADDRESS, true)?;
 
-    let busses: [&AccountInfo; BUS_COUNT] = [
-        bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info,
-        bus_7_info,
-    ];
+    // Load the buses.
+    //
+    // The number of buses is set by the resource config. The buses are passed in order of their ID. Their
+    // leaderboards are merged by a separate crank, so reset fits in the account limit of a transaction.
+    let mut resource_config_data = resource_config_info.data.borrow_mut();
+    let resource_config = ResourceConfig::try_from_bytes_mut(&mut resource_config_data)?;
+    let bus_count = resource_config.bus_count as usize;
+    if busses.len().lt(&bus_count) {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    if busses.len().gt(&bus_count) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    for (id, bus_info) in busses.iter().enumerate() {
+        load_wood_bus(bus_info, id as u64, true)?;
+    }
 
     // Validate enough time has passed since the last reset.
     let mut config_data = config_info.data.borrow_mut();
@@ -44,23 +54,25 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     if config
         .last_reset_at
-        .saturating_add(WOOD_EPOCH_DURATION)
+        .saturating_add(resource_config.epoch_duration)
         .gt(&clock.unix_timestamp)
     {
         return Ok(());
     }
 
     // Update timestamp.
+    let epoch_start_at = config.last_reset_at;
     config.last_reset_at = clock.unix_timestamp;
 
     // Reset bus accounts and calculate reward rates for next epoch.
     let mut top_balance: u64 = 0u64;
     let mut total_remaining_rewards = 0u64;
     let mut total_theoretical_rewards = 0u64;
+    let mut bus_theoretical_rewards = Vec::with_capacity(bus_count);
     
-    for i in 0..BUS_COUNT {
+    for bus_info in busses {
         // Parse bus account.
-        let mut bus_data = busses[i].data.borrow_mut();
+        let mut bus_data = bus_info.data.borrow_mut();
         let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
 
         // Track top balance.
@@ -71,6 +83,9 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         total_remaining_rewards = total_remaining_rewards.saturating_add(bus.rewards);
         total_theoretical_rewards = total_theoretical_rewards.saturating_add(bus.theoretical_rewards);
 
+        // Record the bus's share of the epoch for rebalancing.
+        bus_theoretical_rewards.push(bus.theoretical_rewards);
+
         // Reset bus account for new epoch.
         bus.theoretical_rewards = 0;
         bus.top_balance = 0;
@@ -78,24 +93,64 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
 
     let total_epoch_rewards = config.total_epoch_rewards.saturating_sub(total_remaining_rewards).max(0);
 
-    // Calculate propogation for next epoch.
-    let mut next_epoch_rewards = 0u64;
-    // If total_epoch_rewards is 0, double propogation rate (i.e. 10%).
-    let propogation_rate = if total_epoch_rewards.eq(&0) {
-        WOOD_PROPOGATION_RATE / 2
-    } else {
-        WOOD_PROPOGATION_RATE
-    };
+    // Record the epoch in the history.
+    let mut epoch_history_data = epoch_history_info.data.borrow_mut();
+    let epoch_history = EpochHistory::try_from_bytes_mut(&mut epoch_history_data)?;
+    epoch_history.push(EpochSummary {
+        start_at: epoch_start_at,
+        end_at: config.last_reset_at,
+        theoretical_rewards: total_theoretical_rewards,
+        rewards: total_epoch_rewards,
+        remaining_rewards: total_remaining_rewards,
+        base_reward_rate: config.base_reward_rate,
+        min_difficulty: config.min_difficulty,
+        top_balance,
+    });
+
+    // Calculate the amount to mint.
+    //
+    // The rewards of the epoch have already been paid out to proofs, so they are minted in full unless
+    // the max supply has been reached. The signer is then paid the reset bounty, and the buses are topped up
+    // with no more than the supply left.
+    let mint = Mint::unpack(&mint_info.data.borrow()).expect("Failed to parse mint");
+    let amount = resource_config.remaining_supply(mint.supply).min(total_epoch_rewards);
+    let bounty = resource_config
+        .remaining_supply(mint.supply.saturating_add(amount))
+        .min(resource_config.reset_bounty);
+    let supply = mint.supply.saturating_add(amount).saturating_add(bounty);
+    let remaining_supply = resource_config.remaining_supply(supply);
+    let emission_model = resource_config.emission_model()?;
 
-    for i in 0..BUS_COUNT {
-        let mut bus_data = busses[i].data.borrow_mut();
+    // Store the max bus rewards for the next epoch, if the emission model updates them.
+    if let Some(max_bus_epoch_rewards) = emission_model.next_max_bus_epoch_rewards() {
+        resource_config.max_bus_epoch_rewards = max_bus_epoch_rewards;
+    }
+
+    // Top up bus accounts for the next epoch.
+    let epoch = EmissionEpoch {
+        supply,
+        epoch_rewards: total_epoch_rewards,
+        remaining_rewards: total_remaining_rewards,
+        bus_count: bus_count as u64,
+    };
+    // The emission model decides the rewards of each bus for the next epoch, which are then rebalanced across
+    // buses by their share of the theoretical rewards of the epoch which just ended, up to the model's max.
+    let mut bus_rewards = Vec::with_capacity(bus_count);
+    for bus_info in busses {
+        let bus_remaining_rewards = Bus::try_from_bytes(&bus_info.data.borrow())?.rewards;
+        bus_rewards.push(
+            emission_model
+                .bus_rewards(&epoch, bus_remaining_rewards)
+                .min(remaining_supply.saturating_div(bus_count as u64)),
+        );
+    }
+    let bus_rewards = rebalance_bus_rewards(&bus_rewards, &bus_theoretical_rewards, emission_model.max_bus_rewards(&epoch));
+    let mut next_epoch_rewards = 0u64;
+    for (bus_info, rewards) in busses.iter().zip(bus_rewards) {
+        let mut bus_data = bus_info.data.borrow_mut();
         let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
-        
-        // Distribute remaining rewards to each bus with 5% growth rate.
-        bus.rewards = total_remaining_rewards.saturating_div(BUS_COUNT as u64).saturating_add(
-            bus.rewards.saturating_div(propogation_rate)
-        ).max(MIN_WOOD_EPOCH_REWARDS).min(MAX_WOOD_EPOCH_REWARDS);
-        next_epoch_rewards = next_epoch_rewards.saturating_add(bus.rewards);
+        bus.rewards = rewards;
+        next_epoch_rewards = next_epoch_rewards.saturating_add(rewards);
     }
     
     // Update global top balance.
@@ -103,11 +158,12 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
     
     // Update the rewards for the next epoch.
     config.total_epoch_rewards = next_epoch_rewards;
+    resource_config.epoch_rewards = next_epoch_rewards;
 
     // Update base reward rate for next epoch.
-    let target_rewards = next_epoch_rewards.saturating_div(BUS_COUNT as u64);
-    // Rewards remain unchanged if total_epoch_rewards is 0.
-    if total_epoch_rewards.gt(&0) {
+    let target_rewards = emission_model.target_rewards(next_epoch_rewards, bus_count as u64);
+    // Rewards remain unchanged if total_epoch_rewards is 0, or if the max supply has been reached.
+    if total_epoch_rewards.gt(&0) && next_epoch_rewards.gt(&0) {
         config.base_reward_rate = calculate_new_reward_rate(
             config.base_reward_rate, 
             total_theoretical_rewards, 
@@ -118,37 +174,83 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         );
     }
 
-    // If base reward rate is too low, increment min difficulty by 1 and double base reward rate.
-    if config.base_reward_rate.le(&BASE_WOOD_REWARD_RATE_MIN_THRESHOLD) {
-        config.min_difficulty = config.min_difficulty.checked_add(1).unwrap();
-        config.base_reward_rate = config.base_reward_rate.checked_mul(2).unwrap();
-    }
-
-    // If base reward rate is too high, decrement min difficulty by 1 and halve base reward rate.
-    if config.base_reward_rate.ge(&BASE_WOOD_REWARD_RATE_MAX_THRESHOLD) && config.min_difficulty.gt(&1) {
-        config.min_difficulty = config.min_difficulty.checked_sub(1).unwrap();
-        config.base_reward_rate = config.base_reward_rate.checked_div(2).unwrap();
+    // If base reward rate leaves its bounds, rescale the difficulty target by the same ratio.
+    //
+    // The target moves by a fraction of a bit, so the difficulty and the base reward rate do not oscillate
+    // between whole-bit steps. Without emission there is no target to adjust to, so the difficulty is left
+    // unchanged.
+    if next_epoch_rewards.gt(&0) {
+        let (difficulty_target, base_reward_rate) = adjust_difficulty_target(
+            resource_config.difficulty_target(),
+            config.base_reward_rate,
+            BASE_WOOD_REWARD_RATE_MIN_THRESHOLD,
+            BASE_WOOD_REWARD_RATE_MAX_THRESHOLD,
+        );
+        resource_config.set_difficulty_target(difficulty_target);
+        config.base_reward_rate = base_reward_rate;
+        config.min_difficulty = min_difficulty(difficulty_target);
     }
 
     
     // Fund the treasury token account.
-    solana_program::program::invoke_signed(
-        &spl_token::instruction::mint_to(
-            &spl_token::id(),
-            mint_info.key,
-            treasury_tokens_info.key,
-            treasury_info.key,
-            &[treasury_info.key],
-            total_epoch_rewards,
-        )?,
-        &[
-            token_program.clone(),
-            mint_info.clone(),
-            treasury_tokens_info.clone(),
-            treasury_info.clone(),
-        ],
-        &[&[TREASURY, &[TREASURY_BUMP]]],
-    )?;
+    if amount.gt(&0) {
+        solana_program::program::invoke_signed(
+            &spl_token::instruction::mint_to(
+                &spl_token::id(),
+                mint_info.key,
+                treasury_tokens_info.key,
+                treasury_info.key,
+                &[treasury_info.key],
+                amount,
+            )?,
+            &[
+                token_program.clone(),
+                mint_info.clone(),
+                treasury_tokens_info.clone(),
+                treasury_info.clone(),
+            ],
+            &[&[TREASURY, &[TREASURY_BUMP]]],
+        )?;
+    }
+
+    // Pay the reset bounty to the signer.
+    //
+    // The bounty token account is only validated when a bounty is paid, so a signer without a token account
+    // can still crank the reset.
+    if bounty.gt(&0) {
+        load_token_account(bounty_tokens_info, None, &WOOD_MINT_ADDRESS, true)?;
+        solana_program::program::invoke_signed(
+            &spl_token::instruction::mint_to(
+                &spl_token::id(),
+                mint_info.key,
+                bounty_tokens_info.key,
+                treasury_info.key,
+                &[treasury_info.key],
+                bounty,
+            )?,
+            &[
+                token_program.clone(),
+                mint_info.clone(),
+                bounty_tokens_info.clone(),
+                treasury_info.clone(),
+            ],
+            &[&[TREASURY, &[TREASURY_BUMP]]],
+        )?;
+    }
+
+    // Log the reset.
+    ResetEvent {
+        resource: WOOD_MINT_ADDRESS,
+        reset_at: config.last_reset_at,
+        theoretical_rewards: total_theoretical_rewards,
+        rewards: total_epoch_rewards,
+        remaining_rewards: total_remaining_rewards,
+        base_reward_rate: config.base_reward_rate,
+        min_difficulty: config.min_difficulty,
+        top_balance: config.top_balance,
+        minted: amount,
+    }
+    .log();
 
     Ok(())
 }
\ No newline at end of file
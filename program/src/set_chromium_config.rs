@@ -0,0 +1,74 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    instruction::SetChromiumConfigArgs,
+    loaders::*,
+    state::ChromiumConfig,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+
+/// Sets the CHROMIUM supply policy, creating the CHROMIUM config account if needed.
+pub fn process_set_chromium_config(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = SetChromiumConfigArgs::try_from_bytes(data)?;
+    let max_supply = u64::from_le_bytes(args.max_supply);
+    let epoch_mint_cap = u64::from_le_bytes(args.epoch_mint_cap);
+    let epoch_duration = i64::from_le_bytes(args.epoch_duration);
+    if epoch_duration.le(&0) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Load accounts.
+    let [signer, chromium_config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_program(system_program, system_program::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Initialize CHROMIUM config, if needed.
+    if chromium_config_info.data_is_empty() {
+        load_uninitialized_pda(chromium_config_info, &[CHROMIUM_CONFIG], args.bump, &coal_api::id())?;
+        create_pda(
+            chromium_config_info,
+            &coal_api::id(),
+            8 + size_of::<ChromiumConfig>(),
+            &[CHROMIUM_CONFIG, &[args.bump]],
+            system_program,
+            signer,
+        )?;
+        let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+        let mut chromium_config_data = chromium_config_info.data.borrow_mut();
+        chromium_config_data[0] = ChromiumConfig::discriminator();
+        let chromium_config = ChromiumConfig::try_from_bytes_mut(&mut chromium_config_data)?;
+        chromium_config.epoch_start_at = clock.unix_timestamp;
+    } else {
+        load_chromium_config(chromium_config_info, true)?;
+    }
+
+    // Update CHROMIUM config.
+    let mut chromium_config_data = chromium_config_info.data.borrow_mut();
+    let chromium_config = ChromiumConfig::try_from_bytes_mut(&mut chromium_config_data)?;
+    chromium_config.max_supply = max_supply;
+    chromium_config.epoch_mint_cap = epoch_mint_cap;
+    chromium_config.epoch_duration = epoch_duration;
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    event::SetDelegateEvent,
+    instruction::SetDelegateArgs,
+    loaders::*,
+    state::{DelegateRestriction, Delegation},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+
+/// Adds, updates or revokes a delegate allowed to mine with a proof. The delegation account is created on
+/// the first delegate.
+pub fn process_set_delegate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SetDelegateArgs::try_from_bytes(data)?;
+    let expires_at = i64::from_le_bytes(args.expires_at);
+    let restrictions = u64::from_le_bytes(args.restrictions);
+    if args.delegate.eq(&Pubkey::default()) || restrictions & !DelegateRestriction::MASK != 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Load accounts.
+    let [signer, proof_info, delegation_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof_with_authority(proof_info, signer.key, false)?;
+    load_program(system_program, system_program::id())?;
+
+    // Initialize the delegation account, if needed.
+    if delegation_info.data_is_empty() {
+        load_uninitialized_pda(
+            delegation_info,
+            &[DELEGATION, proof_info.key.as_ref()],
+            args.bump,
+            &coal_api::id(),
+        )?;
+        create_pda(
+            delegation_info,
+            &coal_api::id(),
+            8 + size_of::<Delegation>(),
+            &[DELEGATION, proof_info.key.as_ref(), &[args.bump]],
+            system_program,
+            signer,
+        )?;
+        let mut delegation_data = delegation_info.data.borrow_mut();
+        delegation_data[0] = Delegation::discriminator();
+        let delegation = Delegation::try_from_bytes_mut(&mut delegation_data)?;
+        delegation.proof = *proof_info.key;
+    } else {
+        load_delegation(delegation_info, proof_info.key, true)?;
+    }
+
+    // Update the delegate.
+    //
+    // An expiry in the past revokes the delegate.
+    let mut delegation_data = delegation_info.data.borrow_mut();
+    let delegation = Delegation::try_from_bytes_mut(&mut delegation_data)?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    if expires_at.le(&clock.unix_timestamp) {
+        delegation.remove(&args.delegate);
+    } else if !delegation.set(args.delegate, expires_at, restrictions, clock.unix_timestamp) {
+        return Err(CoalError::TooManyDelegates.into());
+    }
+
+    // Log the delegate.
+    SetDelegateEvent {
+        authority: *signer.key,
+        proof: *proof_info.key,
+        delegate: args.delegate,
+        expires_at,
+        restrictions,
+    }
+    .log();
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    difficulty::{difficulty_target, DIFFICULTY_TARGET_CALIBRATION},
+    emission::{EmissionEpoch, EmissionModelKind},
+    error::CoalError,
+    instruction::SetResourceConfigArgs,
+    loaders::*,
+    state::{Config, ResourceConfig, WoodConfig, BPS_DENOMINATOR},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    system_program,
+};
+use spl_token::state::Mint;
+
+use crate::utils::{create_pda, realloc_pda, AccountDeserialize, Discriminator};
+
+/// Sets the emission parameters of a resource, creating its resource config account if needed.
+pub fn process_set_resource_config(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = SetResourceConfigArgs::try_from_bytes(data)?;
+    let emission_model = EmissionModelKind::try_from(args.emission_model)
+        .or(Err(ProgramError::InvalidInstructionData))?;
+    let max_supply = u64::from_le_bytes(args.max_supply);
+    let min_bus_epoch_rewards = u64::from_le_bytes(args.min_bus_epoch_rewards);
+    let max_bus_epoch_rewards = u64::from_le_bytes(args.max_bus_epoch_rewards);
+    let epoch_rewards_decay = u64::from_le_bytes(args.epoch_rewards_decay);
+    let epoch_duration = i64::from_le_bytes(args.epoch_duration);
+    let bus_count = u64::from_le_bytes(args.bus_count);
+    let reset_bounty = u64::from_le_bytes(args.reset_bounty);
+    let halving_count = u64::from_le_bytes(args.halving_count);
+    if epoch_rewards_decay.gt(&BPS_DENOMINATOR)
+        || min_bus_epoch_rewards.gt(&max_bus_epoch_rewards)
+        || epoch_duration.le(&0)
+        || bus_count.eq(&0)
+        || bus_count.gt(&(MAX_BUS_COUNT as u64))
+        || (emission_model.eq(&EmissionModelKind::Halving) && (max_supply.eq(&0) || halving_count.eq(&0)))
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Load accounts.
+    let [signer, mint_info, resource_config_info, config_info, system_program, bus_infos @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    if mint_info.key.eq(&COAL_MINT_ADDRESS) {
+        load_coal_config(config_info, true)?;
+    } else if mint_info.key.eq(&WOOD_MINT_ADDRESS) {
+        load_wood_config(config_info, true)?;
+    } else {
+        return Err(CoalError::InvalidResource.into());
+    }
+
+    // Validate the buses in use exist.
+    //
+    // Reset loads every bus in use, so a bus count above the initialized buses would stall the resource. The
+    // buses are passed in order of their ID.
+    if bus_infos.len().lt(&(bus_count as usize)) {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    if bus_infos.len().gt(&(bus_count as usize)) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    for (id, bus_info) in bus_infos.iter().enumerate() {
+        if mint_info.key.eq(&COAL_MINT_ADDRESS) {
+            load_coal_bus(bus_info, id as u64, false)?;
+        } else {
+            load_wood_bus(bus_info, id as u64, false)?;
+        }
+    }
+    load_mint(mint_info, *mint_info.key, false)?;
+    load_program(system_program, system_program::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Initialize resource config, if needed.
+    let is_new = resource_config_info.data_is_empty();
+    if is_new {
+        load_uninitialized_pda(
+            resource_config_info,
+            &[RESOURCE_CONFIG, mint_info.key.as_ref()],
+            args.bump,
+            &coal_api::id(),
+        )?;
+        create_pda(
+            resource_config_info,
+            &coal_api::id(),
+            8 + size_of::<ResourceConfig>(),
+            &[RESOURCE_CONFIG, mint_info.key.as_ref(), &[args.bump]],
+            system_program,
+            signer,
+        )?;
+        let mut resource_config_data = resource_config_info.data.borrow_mut();
+        resource_config_data[0] = ResourceConfig::discriminator();
+        let resource_config = ResourceConfig::try_from_bytes_mut(&mut resource_config_data)?;
+        resource_config.resource = *mint_info.key;
+    } else {
+        realloc_pda(
+            resource_config_info,
+            8 + size_of::<ResourceConfig>(),
+            system_program,
+            signer,
+        )?;
+        load_resource_config(resource_config_info, mint_info.key, true)?;
+    }
+
+    // Update resource config.
+    //
+    // Buses cannot be removed, since their remaining rewards are accounted for at reset.
+    let mut resource_config_data = resource_config_info.data.borrow_mut();
+    let resource_config = ResourceConfig::try_from_bytes_mut(&mut resource_config_data)?;
+    if bus_count.lt(&resource_config.bus_count) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    resource_config.emission_model = u8::from(emission_model) as u64;
+    resource_config.max_supply = max_supply;
+    resource_config.min_bus_epoch_rewards = min_bus_epoch_rewards;
+    resource_config.max_bus_epoch_rewards = max_bus_epoch_rewards;
+    resource_config.epoch_rewards_decay = epoch_rewards_decay;
+    resource_config.epoch_duration = epoch_duration;
+    resource_config.bus_count = bus_count;
+    resource_config.reset_bounty = reset_bounty;
+    resource_config.halving_count = halving_count;
+
+    // Initialize the epoch rewards, if needed.
+    //
+    // The COAL buses were last topped up by the halving schedule, so the epoch rewards are derived from the
+    // configured emission model. The WOOD epoch rewards are tracked by the WOOD config.
+    if is_new && mint_info.key.eq(&COAL_MINT_ADDRESS) {
+        let mint = Mint::unpack(&mint_info.data.borrow())?;
+        let epoch = EmissionEpoch {
+            supply: mint.supply,
+            epoch_rewards: 0,
+            remaining_rewards: 0,
+            bus_count,
+        };
+        resource_config.epoch_rewards = resource_config
+            .emission_model()?
+            .max_bus_rewards(&epoch)
+            .saturating_mul(bus_count);
+    }
+
+    // Initialize the difficulty target, if needed.
+    //
+    // The target starts at the whole-bit min difficulty, and the base reward rate is calibrated so the emission
+    // does not increase with the smooth difficulty multiplier.
+    if resource_config.difficulty_target().eq(&0) {
+        let mut config_data = config_info.data.borrow_mut();
+        let (min_difficulty, base_reward_rate) = if mint_info.key.eq(&COAL_MINT_ADDRESS) {
+            let config = Config::try_from_bytes_mut(&mut config_data)?;
+            (config.min_difficulty, &mut config.base_reward_rate)
+        } else {
+            let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+            (config.min_difficulty, &mut config.base_reward_rate)
+        };
+        resource_config.set_difficulty_target(difficulty_target(min_difficulty));
+        *base_reward_rate = (*base_reward_rate as u128)
+            .saturating_mul(DIFFICULTY_TARGET_CALIBRATION as u128)
+            .saturating_div(BPS_DENOMINATOR as u128)
+            .max(1) as u64;
+    }
+
+    Ok(())
+}
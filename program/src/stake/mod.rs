@@ -6,10 +6,14 @@ use solana_program::{
 
 use crate::utils::Discriminator;
 
+pub use stake_chromium::*;
 use stake_coal::*;
 use stake_wood::*;
+pub use unstake_chromium::*;
+mod stake_chromium;
 mod stake_coal;
 mod stake_wood;
+mod unstake_chromium;
 
 pub fn process_stake<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
     let config_info = &accounts[1];
@@ -0,0 +1,90 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    event::StakeEvent,
+    instruction::StakeChromiumArgs,
+    loaders::*,
+    state::ChromiumStake,
+};
+use coal_utils::spl::transfer;
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, system_program, sysvar::Sysvar,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+
+/// Stakes CHROMIUM against a proof to boost its mining rewards. The CHROMIUM stake account is created
+/// on the first stake.
+pub fn process_stake_chromium(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = StakeChromiumArgs::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let [signer, proof_info, chromium_stake_info, sender_info, treasury_tokens_info, token_program, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof_with_authority(proof_info, signer.key, false)?;
+    load_token_account(sender_info, Some(signer.key), &CHROMIUM_MINT_ADDRESS, true)?;
+    load_token_account(treasury_tokens_info, Some(&TREASURY_ADDRESS), &CHROMIUM_MINT_ADDRESS, true)?;
+    load_program(token_program, spl_token::id())?;
+    load_program(system_program, system_program::id())?;
+
+    // Initialize the CHROMIUM stake account, if needed.
+    if chromium_stake_info.data_is_empty() {
+        load_uninitialized_pda(
+            chromium_stake_info,
+            &[CHROMIUM_STAKE, proof_info.key.as_ref()],
+            args.bump,
+            &coal_api::id(),
+        )?;
+        create_pda(
+            chromium_stake_info,
+            &coal_api::id(),
+            8 + size_of::<ChromiumStake>(),
+            &[CHROMIUM_STAKE, proof_info.key.as_ref(), &[args.bump]],
+            system_program,
+            signer,
+        )?;
+        let mut chromium_stake_data = chromium_stake_info.data.borrow_mut();
+        chromium_stake_data[0] = ChromiumStake::discriminator();
+        let chromium_stake = ChromiumStake::try_from_bytes_mut(&mut chromium_stake_data)?;
+        chromium_stake.proof = *proof_info.key;
+    } else {
+        load_chromium_stake(chromium_stake_info, proof_info.key, true)?;
+    }
+
+    // Update the stake balance and deposit timestamp.
+    let mut chromium_stake_data = chromium_stake_info.data.borrow_mut();
+    let chromium_stake = ChromiumStake::try_from_bytes_mut(&mut chromium_stake_data)?;
+    chromium_stake.balance = chromium_stake.balance.checked_add(amount).unwrap();
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    chromium_stake.last_stake_at = clock.unix_timestamp;
+    let balance = chromium_stake.balance;
+    drop(chromium_stake_data);
+
+    // Transfer tokens from signer to treasury.
+    transfer(
+        signer,
+        sender_info,
+        treasury_tokens_info,
+        token_program,
+        amount,
+    )?;
+
+    // Log the stake.
+    StakeEvent {
+        authority: *signer.key,
+        resource: CHROMIUM_MINT_ADDRESS,
+        amount,
+        balance,
+    }
+    .log();
+
+    Ok(())
+}
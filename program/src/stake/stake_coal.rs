@@ -1,4 +1,4 @@
-use coal_api::{consts::*, instruction::StakeArgs, loaders::*, state::Proof};
+use coal_api::{consts::*, event::StakeEvent, instruction::StakeArgs, loaders::*, state::Proof};
 use coal_utils::spl::transfer;
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
@@ -31,6 +31,8 @@ pub fn process_stake_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
     // Update deposit timestamp.
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     proof.last_stake_at = clock.unix_timestamp;
+    let balance = proof.balance;
+    drop(proof_data);
 
     // Transfer tokens from signer to treasury.
     transfer(
@@ -41,5 +43,14 @@ pub fn process_stake_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
         amount,
     )?;
 
+    // Log the stake.
+    StakeEvent {
+        authority: *signer.key,
+        resource: COAL_MINT_ADDRESS,
+        amount,
+        balance,
+    }
+    .log();
+
     Ok(())
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use coal_api::{consts::*, instruction::StakeArgs, loaders::*, state::ProofV2};
+use coal_api::{consts::*, event::StakeEvent, instruction::StakeArgs, loaders::*, state::ProofV2};
 use coal_utils::spl::transfer;
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
@@ -30,6 +30,8 @@ pub fn process_stake_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
     // Update deposit timestamp.
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     proof.last_stake_at = clock.unix_timestamp;
+    let balance = proof.balance;
+    drop(proof_data);
 
     // Transfer tokens from signer to treasury.
     transfer(
@@ -40,5 +42,14 @@ pub fn process_stake_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
         amount,
     )?;
 
+    // Log the stake.
+    StakeEvent {
+        authority: *signer.key,
+        resource: WOOD_MINT_ADDRESS,
+        amount,
+        balance,
+    }
+    .log();
+
     Ok(())
 }
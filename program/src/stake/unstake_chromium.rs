@@ -0,0 +1,104 @@
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    event::{UnstakeEvent, WithdrawEvent},
+    instruction::StakeArgs,
+    loaders::*,
+    state::ChromiumStake,
+};
+use coal_utils::spl::transfer_signed;
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, sysvar::Sysvar,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Unstakes CHROMIUM from a proof. The unstaked CHROMIUM stops boosting mining rewards immediately, and
+/// can be withdrawn once the cooldown has ended.
+pub fn process_unstake_chromium(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = StakeArgs::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let [signer, proof_info, chromium_stake_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof_with_authority(proof_info, signer.key, false)?;
+    load_chromium_stake(chromium_stake_info, proof_info.key, true)?;
+
+    // Move the amount to the unstaking balance.
+    let mut chromium_stake_data = chromium_stake_info.data.borrow_mut();
+    let chromium_stake = ChromiumStake::try_from_bytes_mut(&mut chromium_stake_data)?;
+    if amount.gt(&chromium_stake.balance) {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    chromium_stake.unstake(amount, clock.unix_timestamp);
+
+    // Log the unstake.
+    UnstakeEvent {
+        authority: *signer.key,
+        resource: CHROMIUM_MINT_ADDRESS,
+        amount,
+        balance: chromium_stake.balance,
+        unstaking_balance: chromium_stake.unstaking_balance,
+        withdrawable_at: chromium_stake.withdrawable_at,
+    }
+    .log();
+
+    Ok(())
+}
+
+/// Withdraws unstaked CHROMIUM from the treasury once the cooldown has ended.
+pub fn process_withdraw_chromium(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, proof_info, chromium_stake_info, beneficiary_info, treasury_info, treasury_tokens_info, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof_with_authority(proof_info, signer.key, false)?;
+    load_chromium_stake(chromium_stake_info, proof_info.key, true)?;
+    load_token_account(beneficiary_info, None, &CHROMIUM_MINT_ADDRESS, true)?;
+    load_treasury(treasury_info, false)?;
+    load_token_account(treasury_tokens_info, Some(&TREASURY_ADDRESS), &CHROMIUM_MINT_ADDRESS, true)?;
+    load_program(token_program, spl_token::id())?;
+
+    // Validate the cooldown has ended.
+    let mut chromium_stake_data = chromium_stake_info.data.borrow_mut();
+    let chromium_stake = ChromiumStake::try_from_bytes_mut(&mut chromium_stake_data)?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    if chromium_stake.withdrawable_at.gt(&clock.unix_timestamp) {
+        return Err(CoalError::CooldownActive.into());
+    }
+
+    // Clear the unstaking balance.
+    let amount = chromium_stake.unstaking_balance;
+    chromium_stake.unstaking_balance = 0;
+    drop(chromium_stake_data);
+
+    // Transfer tokens from treasury to beneficiary.
+    transfer_signed(
+        treasury_info,
+        treasury_tokens_info,
+        beneficiary_info,
+        token_program,
+        amount,
+        &[&[TREASURY, &[TREASURY_BUMP]]],
+    )?;
+
+    // Log the withdrawal.
+    WithdrawEvent {
+        authority: *signer.key,
+        resource: CHROMIUM_MINT_ADDRESS,
+        beneficiary: *beneficiary_info.key,
+        amount,
+    }
+    .log();
+
+    Ok(())
+}
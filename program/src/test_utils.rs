@@ -0,0 +1,45 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{deserialize, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER},
+    pubkey::Pubkey,
+};
+
+/// An account passed to a processor in tests.
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Serializes the accounts the way the runtime passes them to the program. Account infos read from the
+/// buffer can be reallocated, unlike account infos backed by plain vectors.
+pub fn serialize(accounts: &[TestAccount]) -> Vec<u64> {
+    let mut bytes = (accounts.len() as u64).to_le_bytes().to_vec();
+    for account in accounts {
+        bytes.extend_from_slice(&[NON_DUP_MARKER, account.is_signer as u8, account.is_writable as u8, 0]);
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(account.key.as_ref());
+        bytes.extend_from_slice(account.owner.as_ref());
+        bytes.extend_from_slice(&account.lamports.to_le_bytes());
+        bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&account.data);
+        bytes.resize((bytes.len() + MAX_PERMITTED_DATA_INCREASE).next_multiple_of(8), 0);
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+    }
+    bytes.extend_from_slice(&0u64.to_le_bytes());
+    bytes.extend_from_slice(coal_api::id().as_ref());
+    bytes.resize(bytes.len().next_multiple_of(8), 0);
+    bytes
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// Returns the account infos of a buffer built by [`serialize`].
+pub fn account_infos(buffer: &mut [u64]) -> Vec<AccountInfo<'_>> {
+    // SAFETY: the buffer is aligned and laid out like the runtime input.
+    unsafe { deserialize(buffer.as_mut_ptr() as *mut u8).1 }
+}
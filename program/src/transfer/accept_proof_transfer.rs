@@ -0,0 +1,49 @@
+use coal_api::{
+    event::AcceptProofTransferEvent, instruction::TransferProofArgs, loaders::*, state::ProofTransfer,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::AccountDeserialize;
+
+use super::migrate_proof;
+
+/// Accept proof transfer moves a proof to the new authority of a proposed transfer. The new authority pays
+/// for the new accounts, and the rent of the old accounts is refunded to the old authority.
+pub fn process_accept_proof_transfer(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = TransferProofArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, authority_info, migrate_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [proof_info, new_proof_info, _, _, _, _, proof_transfer_info, ..] = migrate_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_proof_transfer(proof_transfer_info, proof_info.key, true)?;
+
+    // Validate the signer is the proposed authority.
+    let proof_transfer_data = proof_transfer_info.data.borrow();
+    let proof_transfer = ProofTransfer::try_from_bytes(&proof_transfer_data)?;
+    if proof_transfer.new_authority.ne(signer.key) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    drop(proof_transfer_data);
+
+    // Move the proof. The proof transfer is closed along with the old proof.
+    migrate_proof(signer, authority_info, signer.key, migrate_accounts, args)?;
+
+    // Log the transfer.
+    AcceptProofTransferEvent {
+        authority: *authority_info.key,
+        new_authority: *signer.key,
+        proof: *proof_info.key,
+        new_proof: *new_proof_info.key,
+    }
+    .log();
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use coal_api::{consts::*, event::CancelProofTransferEvent, loaders::*};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::close::close_proof_account;
+
+/// Cancel proof transfer withdraws a proposed transfer of a proof and refunds its rent to the authority.
+pub fn process_cancel_proof_transfer(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, proof_info, proof_transfer_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof_with_authority(proof_info, signer.key, false)?;
+    load_proof_transfer(proof_transfer_info, proof_info.key, true)?;
+
+    // Close the proof transfer.
+    close_proof_account(proof_transfer_info, PROOF_TRANSFER, proof_info.key, signer)?;
+
+    // Log the cancellation.
+    CancelProofTransferEvent {
+        authority: *signer.key,
+        proof: *proof_info.key,
+    }
+    .log();
+
+    Ok(())
+}
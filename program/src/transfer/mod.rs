@@ -0,0 +1,264 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    instruction::TransferProofArgs,
+    loaders::*,
+    state::{ChromiumStake, MinerStats, Proof, ProofV2, Reprocessor},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    close::{close_chromium_stake, close_proof_account},
+    utils::{create_pda, AccountDeserialize, Discriminator},
+};
+
+pub use accept_proof_transfer::*;
+pub use cancel_proof_transfer::*;
+pub use propose_proof_transfer::*;
+pub use transfer_proof::*;
+mod accept_proof_transfer;
+mod cancel_proof_transfer;
+mod propose_proof_transfer;
+mod transfer_proof;
+
+/// Moves a proof, its miner stats and its CHROMIUM stake to the proof address of the new authority, and closes
+/// the old accounts. The balance and lifetime stats move with the proof. The miner moves to the new authority
+/// if it was the old authority.
+///
+/// The staked CHROMIUM stays in the treasury, only the stake account is moved to the new proof. The miner
+/// stats must move along with the proof, otherwise the lifetime stats could be reprocessed twice. For the same
+/// reason, a proof cannot move while the reprocessor of the old authority is bound to it. The pending
+/// proof transfer and the delegation of the old proof are closed, so a proof reopened at the old address
+/// does not inherit them.
+pub(crate) fn migrate_proof<'info>(
+    payer: &AccountInfo<'info>,
+    authority_info: &AccountInfo<'info>,
+    new_authority: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    args: &TransferProofArgs,
+) -> ProgramResult {
+    // Load accounts.
+    let [proof_info, new_proof_info, miner_stats_info, new_miner_stats_info, chromium_stake_info, new_chromium_stake_info, proof_transfer_info, delegation_info, reprocessor_info, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !authority_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let is_coal_proof = proof_info
+        .data
+        .borrow()
+        .first()
+        .is_some_and(|d| d.eq(&Proof::discriminator()));
+    let seed = if is_coal_proof {
+        load_coal_proof(proof_info, authority_info.key, true)?;
+        COAL_PROOF
+    } else {
+        load_proof_v2(proof_info, authority_info.key, &WOOD_MINT_ADDRESS, true)?;
+        WOOD_PROOF
+    };
+    load_uninitialized_pda(
+        new_proof_info,
+        &[seed, new_authority.as_ref()],
+        args.proof_bump,
+        &coal_api::id(),
+    )?;
+    let miner_stats_address =
+        Pubkey::find_program_address(&[MINER_STATS, proof_info.key.as_ref()], &coal_api::id()).0;
+    let chromium_stake_address =
+        Pubkey::find_program_address(&[CHROMIUM_STAKE, proof_info.key.as_ref()], &coal_api::id()).0;
+    if miner_stats_info.key.ne(&miner_stats_address) || chromium_stake_info.key.ne(&chromium_stake_address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Validate the proof has no pending reprocess. Legacy reprocessors are not bound to a proof.
+    let reprocessor_address =
+        Pubkey::find_program_address(&[REPROCESSOR, authority_info.key.as_ref()], &coal_api::id()).0;
+    if reprocessor_info.key.ne(&reprocessor_address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if reprocessor_info.owner.eq(&coal_api::id())
+        && !reprocessor_info.data_is_empty()
+        && reprocessor_info.data_len().ne(&Reprocessor::LEGACY_SIZE)
+    {
+        let reprocessor_data = reprocessor_info.data.borrow();
+        let reprocessor = Reprocessor::try_from_bytes(&reprocessor_data)?;
+        if reprocessor.proof.eq(proof_info.key) {
+            return Err(CoalError::ReprocessPending.into());
+        }
+    }
+
+    // Copy the proof to the new authority.
+    create_pda(
+        new_proof_info,
+        &coal_api::id(),
+        proof_info.data_len(),
+        &[seed, new_authority.as_ref(), &[args.proof_bump]],
+        system_program,
+        payer,
+    )?;
+    let mut new_proof_data = new_proof_info.data.borrow_mut();
+    new_proof_data.copy_from_slice(&proof_info.data.borrow());
+    if is_coal_proof {
+        let proof = Proof::try_from_bytes_mut(&mut new_proof_data)?;
+        proof.authority = *new_authority;
+        if proof.miner.eq(authority_info.key) {
+            proof.miner = *new_authority;
+        }
+    } else {
+        let proof = ProofV2::try_from_bytes_mut(&mut new_proof_data)?;
+        proof.authority = *new_authority;
+        if proof.miner.eq(authority_info.key) {
+            proof.miner = *new_authority;
+        }
+    }
+    drop(new_proof_data);
+
+    // Copy the miner stats to the new proof, if any.
+    if !miner_stats_info.data_is_empty() {
+        load_miner_stats(miner_stats_info, proof_info.key, true)?;
+        load_uninitialized_pda(
+            new_miner_stats_info,
+            &[MINER_STATS, new_proof_info.key.as_ref()],
+            args.miner_stats_bump,
+            &coal_api::id(),
+        )?;
+        create_pda(
+            new_miner_stats_info,
+            &coal_api::id(),
+            miner_stats_info.data_len(),
+            &[MINER_STATS, new_proof_info.key.as_ref(), &[args.miner_stats_bump]],
+            system_program,
+            payer,
+        )?;
+        let mut new_miner_stats_data = new_miner_stats_info.data.borrow_mut();
+        new_miner_stats_data.copy_from_slice(&miner_stats_info.data.borrow());
+        MinerStats::try_from_bytes_mut(&mut new_miner_stats_data)?.proof = *new_proof_info.key;
+        drop(new_miner_stats_data);
+
+        // Close the old miner stats.
+        miner_stats_info.realloc(0, true)?;
+        **authority_info.lamports.borrow_mut() += miner_stats_info.lamports();
+        **miner_stats_info.lamports.borrow_mut() = 0;
+    }
+
+    // Move the CHROMIUM stake to the new proof, if any.
+    if !chromium_stake_info.data_is_empty() {
+        load_chromium_stake(chromium_stake_info, proof_info.key, true)?;
+        load_uninitialized_pda(
+            new_chromium_stake_info,
+            &[CHROMIUM_STAKE, new_proof_info.key.as_ref()],
+            args.chromium_stake_bump,
+            &coal_api::id(),
+        )?;
+        create_pda(
+            new_chromium_stake_info,
+            &coal_api::id(),
+            8 + size_of::<ChromiumStake>(),
+            &[CHROMIUM_STAKE, new_proof_info.key.as_ref(), &[args.chromium_stake_bump]],
+            system_program,
+            payer,
+        )?;
+        let mut chromium_stake_data = chromium_stake_info.data.borrow_mut();
+        let chromium_stake = ChromiumStake::try_from_bytes_mut(&mut chromium_stake_data)?;
+        let mut new_chromium_stake_data = new_chromium_stake_info.data.borrow_mut();
+        new_chromium_stake_data[0] = ChromiumStake::discriminator();
+        let new_chromium_stake = ChromiumStake::try_from_bytes_mut(&mut new_chromium_stake_data)?;
+        new_chromium_stake.proof = *new_proof_info.key;
+        new_chromium_stake.balance = chromium_stake.balance;
+        new_chromium_stake.last_stake_at = chromium_stake.last_stake_at;
+        new_chromium_stake.unstaking_balance = chromium_stake.unstaking_balance;
+        new_chromium_stake.withdrawable_at = chromium_stake.withdrawable_at;
+        chromium_stake.balance = 0;
+        chromium_stake.unstaking_balance = 0;
+    }
+
+    // Close the old CHROMIUM stake, the pending proof transfer and the delegation of the old proof, if any.
+    close_chromium_stake(chromium_stake_info, proof_info.key, authority_info)?;
+    close_proof_account(proof_transfer_info, PROOF_TRANSFER, proof_info.key, authority_info)?;
+    close_proof_account(delegation_info, DELEGATION, proof_info.key, authority_info)?;
+
+    // Close the old proof.
+    proof_info.realloc(0, true)?;
+    **authority_info.lamports.borrow_mut() += proof_info.lamports();
+    **proof_info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::system_program;
+
+    use crate::test_utils::{account_infos, serialize, TestAccount};
+
+    use super::*;
+
+    fn account(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> TestAccount {
+        TestAccount {
+            key,
+            owner,
+            lamports: 1_000,
+            data,
+            is_signer: false,
+            is_writable: true,
+        }
+    }
+
+    fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, &coal_api::id())
+    }
+
+    #[test]
+    fn test_migrate_proof_with_pending_reprocess() {
+        let authority = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+        let proof = pda(&[COAL_PROOF, authority.as_ref()]).0;
+        let new_proof = pda(&[COAL_PROOF, new_authority.as_ref()]);
+        let new_miner_stats = pda(&[MINER_STATS, new_proof.0.as_ref()]);
+        let new_chromium_stake = pda(&[CHROMIUM_STAKE, new_proof.0.as_ref()]);
+
+        // Open a proof with a reprocess bound to it.
+        let mut proof_data = vec![0; 8 + size_of::<Proof>()];
+        proof_data[0] = Proof::discriminator();
+        Proof::try_from_bytes_mut(&mut proof_data).unwrap().authority = authority;
+        let mut reprocessor_data = vec![0; 8 + size_of::<Reprocessor>()];
+        reprocessor_data[0] = Reprocessor::discriminator();
+        let reprocessor = Reprocessor::try_from_bytes_mut(&mut reprocessor_data).unwrap();
+        reprocessor.authority = authority;
+        reprocessor.proof = proof;
+
+        let system = system_program::id();
+        let mut signer = account(authority, system, vec![]);
+        signer.is_signer = true;
+        let mut buffer = serialize(&[
+            signer,
+            account(proof, coal_api::id(), proof_data),
+            account(new_proof.0, system, vec![]),
+            account(pda(&[MINER_STATS, proof.as_ref()]).0, system, vec![]),
+            account(new_miner_stats.0, system, vec![]),
+            account(pda(&[CHROMIUM_STAKE, proof.as_ref()]).0, system, vec![]),
+            account(new_chromium_stake.0, system, vec![]),
+            account(pda(&[PROOF_TRANSFER, proof.as_ref()]).0, system, vec![]),
+            account(pda(&[DELEGATION, proof.as_ref()]).0, system, vec![]),
+            account(pda(&[REPROCESSOR, authority.as_ref()]).0, coal_api::id(), reprocessor_data),
+            account(system, system, vec![]),
+        ]);
+        let infos = account_infos(&mut buffer);
+        let args = TransferProofArgs {
+            proof_bump: new_proof.1,
+            miner_stats_bump: new_miner_stats.1,
+            chromium_stake_bump: new_chromium_stake.1,
+        };
+        assert_eq!(
+            migrate_proof(&infos[0], &infos[0], &new_authority, &infos[1..], &args),
+            Err(CoalError::ReprocessPending.into())
+        );
+    }
+}
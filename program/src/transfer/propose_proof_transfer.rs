@@ -0,0 +1,70 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    event::ProposeProofTransferEvent,
+    instruction::ProposeProofTransferArgs,
+    loaders::*,
+    state::ProofTransfer,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+
+/// Propose proof transfer records a transfer of a proof to a new authority. The transfer is executed once
+/// the new authority accepts it. Proposing again replaces the new authority.
+pub fn process_propose_proof_transfer(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = ProposeProofTransferArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, new_authority_info, proof_info, proof_transfer_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any(new_authority_info, false)?;
+    load_any_proof_with_authority(proof_info, signer.key, false)?;
+    load_program(system_program, system_program::id())?;
+
+    // Initialize the proof transfer account, if needed.
+    if proof_transfer_info.data_is_empty() {
+        load_uninitialized_pda(
+            proof_transfer_info,
+            &[PROOF_TRANSFER, proof_info.key.as_ref()],
+            args.bump,
+            &coal_api::id(),
+        )?;
+        create_pda(
+            proof_transfer_info,
+            &coal_api::id(),
+            8 + size_of::<ProofTransfer>(),
+            &[PROOF_TRANSFER, proof_info.key.as_ref(), &[args.bump]],
+            system_program,
+            signer,
+        )?;
+        let mut proof_transfer_data = proof_transfer_info.data.borrow_mut();
+        proof_transfer_data[0] = ProofTransfer::discriminator();
+        let proof_transfer = ProofTransfer::try_from_bytes_mut(&mut proof_transfer_data)?;
+        proof_transfer.proof = *proof_info.key;
+    } else {
+        load_proof_transfer(proof_transfer_info, proof_info.key, true)?;
+    }
+
+    // Record the new authority.
+    let mut proof_transfer_data = proof_transfer_info.data.borrow_mut();
+    let proof_transfer = ProofTransfer::try_from_bytes_mut(&mut proof_transfer_data)?;
+    proof_transfer.new_authority = *new_authority_info.key;
+
+    // Log the proposal.
+    ProposeProofTransferEvent {
+        authority: *signer.key,
+        new_authority: *new_authority_info.key,
+        proof: *proof_info.key,
+    }
+    .log();
+
+    Ok(())
+}
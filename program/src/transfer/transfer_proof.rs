@@ -0,0 +1,36 @@
+use coal_api::{event::TransferProofEvent, instruction::TransferProofArgs, loaders::*};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use super::migrate_proof;
+
+/// Transfer proof immediately moves a proof to a new authority, without its acceptance.
+pub fn process_transfer_proof(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = TransferProofArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, new_authority_info, migrate_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [proof_info, new_proof_info, ..] = migrate_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any(new_authority_info, false)?;
+
+    // Move the proof.
+    migrate_proof(signer, signer, new_authority_info.key, migrate_accounts, args)?;
+
+    // Log the transfer.
+    TransferProofEvent {
+        authority: *signer.key,
+        new_authority: *new_authority_info.key,
+        proof: *proof_info.key,
+        new_proof: *new_proof_info.key,
+    }
+    .log();
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use coal_api::{consts::*, instruction::UnequipArgs, loaders::*};
+use coal_api::{consts::*, event::UnequipEvent, instruction::UnequipArgs, loaders::*};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, system_program
 };
@@ -79,5 +79,13 @@ pub fn process_unequip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data:
 	  .system_program(Some(system_program))
 	  .invoke_signed(&[signer_seeds])?;
 
+    // Log the unequip.
+    UnequipEvent {
+        authority: *signer.key,
+        asset: *asset_info.key,
+        durability,
+    }
+    .log();
+
 	Ok(())
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use coal_api::{loaders::*, state::Proof};
+use coal_api::{consts::COAL_MINT_ADDRESS, event::UpdateEvent, loaders::*, state::Proof};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
 };
@@ -23,5 +23,13 @@ pub fn process_update_coal<'a, 'info>(
     let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
     proof.miner = *miner_info.key;
 
+    // Log the update.
+    UpdateEvent {
+        authority: *signer.key,
+        resource: COAL_MINT_ADDRESS,
+        miner: *miner_info.key,
+    }
+    .log();
+
     Ok(())
 }
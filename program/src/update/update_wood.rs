@@ -1,4 +1,4 @@
-use coal_api::{consts::WOOD_MINT_ADDRESS, loaders::*, state::ProofV2};
+use coal_api::{consts::WOOD_MINT_ADDRESS, event::UpdateEvent, loaders::*, state::ProofV2};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
 };
@@ -22,5 +22,13 @@ pub fn process_update_wood<'a, 'info>(
     let proof = ProofV2::try_from_bytes_mut(&mut proof_data)?;
     proof.miner = *miner_info.key;
 
+    // Log the update.
+    UpdateEvent {
+        authority: *signer.key,
+        resource: WOOD_MINT_ADDRESS,
+        miner: *miner_info.key,
+    }
+    .log();
+
     Ok(())
 }
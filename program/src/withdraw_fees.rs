@@ -0,0 +1,60 @@
+use std::mem::size_of;
+
+use coal_api::{consts::*, event::WithdrawFeesEvent, loaders::*, state::Treasury};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    rent::Rent,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::utils::{realloc_pda, AccountDeserialize};
+
+/// Withdraws the reprocess fees collected by the treasury to the beneficiary. The treasury always keeps
+/// enough lamports to stay rent exempt.
+pub fn process_withdraw_fees(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, treasury_info, beneficiary_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_treasury(treasury_info, true)?;
+    load_program(system_program, system_program::id())?;
+    if !beneficiary_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Calculate the withdrawable amount.
+    realloc_pda(treasury_info, 8 + size_of::<Treasury>(), system_program, signer)?;
+    let mut treasury_data = treasury_info.data.borrow_mut();
+    let treasury = Treasury::try_from_bytes_mut(&mut treasury_data)?;
+    let rent = Rent::get()?;
+    let available = treasury_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(treasury_info.data_len()));
+    let amount = treasury.unwithdrawn_fees().min(available);
+    treasury.fees_withdrawn = treasury.fees_withdrawn.saturating_add(amount);
+    let fees_withdrawn = treasury.fees_withdrawn;
+    drop(treasury_data);
+
+    // Transfer lamports from treasury to beneficiary.
+    **treasury_info.lamports.borrow_mut() -= amount;
+    **beneficiary_info.lamports.borrow_mut() += amount;
+
+    // Log the withdrawal.
+    WithdrawFeesEvent {
+        beneficiary: *beneficiary_info.key,
+        amount,
+        fees_withdrawn,
+    }
+    .log();
+
+    Ok(())
+}
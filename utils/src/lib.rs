@@ -74,6 +74,41 @@ pub fn create_pda<'a, 'info>(
     Ok(())
 }
 
+/// Grows a program-owned pda to the given size, funding its rent exemption from the payer.
+/// New bytes are zero-initialized.
+#[inline(always)]
+pub fn realloc_pda<'a, 'info>(
+    target_account: &'a AccountInfo<'info>,
+    space: usize,
+    system_program: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+) -> ProgramResult {
+    if target_account.data_len().ge(&space) {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?;
+    let rent_exempt_balance = rent
+        .minimum_balance(space)
+        .saturating_sub(target_account.lamports());
+    if rent_exempt_balance.gt(&0) {
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                payer.key,
+                target_account.key,
+                rent_exempt_balance,
+            ),
+            &[
+                payer.clone(),
+                target_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    target_account.realloc(space, true)
+}
+
 pub trait Discriminator {
     fn discriminator() -> u8;
 }
@@ -122,6 +157,22 @@ macro_rules! impl_account_from_bytes {
     };
 }
 
+#[macro_export]
+macro_rules! impl_event_log {
+    ($struct_name:ident) => {
+        impl $struct_name {
+            /// Logs the event as program data, prefixed by its discriminator.
+            pub fn log(&self) {
+                solana_program::log::sol_log_data(&[&[
+                    &[Self::discriminator()],
+                    bytemuck::bytes_of(self),
+                ]
+                .concat()]);
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! impl_instruction_from_bytes {
     ($struct_name:ident) => {